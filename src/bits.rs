@@ -0,0 +1,492 @@
+//! Bitwise and shift operations.
+//!
+//! Some operations can never produce the excluded value, in which case the operator traits are
+//! implemented with `Self` as output:
+//!
+//! - `NonMaxUX & x` is never all ones, and neither is `NonMaxUX << n` nor `NonMaxUX >> n`.
+//! - `NonMinUX | x` is never zero.
+//! - `NonMaxIX << n` and `NonMaxIX >> n` are never `MAX`.
+//! - `NonMinIX >> n` is never `MIN`, since an arithmetic shift preserves the sign bit.
+//!
+//! All other operations are only available through their `checked_*` variants.
+//!
+//! Similarly, the bit-introspection methods have a refined return type if the invariant allows
+//! it. For example, a `NonMaxUX` always has at least one zero bit and a `NonMinUX` always has at
+//! least one one bit.
+
+use crate::{NonMaxI128, NonMaxI16, NonMaxI32, NonMaxI64, NonMaxI8, NonMaxIsize};
+use crate::{NonMaxU128, NonMaxU16, NonMaxU32, NonMaxU64, NonMaxU8, NonMaxUsize};
+use crate::{NonMinI128, NonMinI16, NonMinI32, NonMinI64, NonMinI8, NonMinIsize};
+use crate::{NonMinU128, NonMinU16, NonMinU32, NonMinU64, NonMinU8, NonMinUsize};
+use core::num::NonZeroU32;
+
+macro_rules! impl_bit_counts {
+    ([$($gen:tt)*] $struct:ty, $prim:ty) => {
+        impl<$($gen)*> $struct {
+            /// Returns the number of leading zeros in the binary representation of `self`.
+            #[inline]
+            pub fn leading_zeros(self) -> u32 {
+                <$prim>::leading_zeros(self.get())
+            }
+
+            /// Returns the number of trailing zeros in the binary representation of `self`.
+            #[inline]
+            pub fn trailing_zeros(self) -> u32 {
+                <$prim>::trailing_zeros(self.get())
+            }
+
+            /// Returns the logarithm of `self` rounded down with base 2, or `None` if `self` is
+            /// not positive.
+            #[inline]
+            pub fn checked_ilog2(self) -> Option<u32> {
+                <$prim>::checked_ilog2(self.get())
+            }
+
+            /// Returns the logarithm of `self` rounded down with base 10, or `None` if `self` is
+            /// not positive.
+            #[inline]
+            pub fn checked_ilog10(self) -> Option<u32> {
+                <$prim>::checked_ilog10(self.get())
+            }
+        }
+    };
+}
+
+macro_rules! impl_fallible_ilog {
+    ($struct:ident) => {
+        impl $struct {
+            /// Returns the logarithm of `self` rounded down with base 2.
+            ///
+            /// # Panics
+            /// Panics if `self` is not positive.
+            #[inline]
+            #[track_caller]
+            pub fn ilog2(self) -> u32 {
+                self.get().ilog2()
+            }
+
+            /// Returns the logarithm of `self` rounded down with base 10.
+            ///
+            /// # Panics
+            /// Panics if `self` is not positive.
+            #[inline]
+            #[track_caller]
+            pub fn ilog10(self) -> u32 {
+                self.get().ilog10()
+            }
+        }
+    };
+}
+
+macro_rules! impl_checked_bit_ops {
+    ([$($gen:tt)*] $struct:ty, $prim:ty) => {
+        impl<$($gen)*> $struct {
+            doc_comment! {
+                concat!("Checked bitwise and. Computes `self & rhs`, returning `None` if the result ",
+                "is not a valid `", stringify!($struct), "`."),
+                #[inline]
+                pub fn checked_bitand(self, rhs: $prim) -> Option<Self> {
+                    Self::new(self.get() & rhs)
+                }
+            }
+
+            doc_comment! {
+                concat!("Checked bitwise or. Computes `self | rhs`, returning `None` if the result ",
+                "is not a valid `", stringify!($struct), "`."),
+                #[inline]
+                pub fn checked_bitor(self, rhs: $prim) -> Option<Self> {
+                    Self::new(self.get() | rhs)
+                }
+            }
+
+            doc_comment! {
+                concat!("Checked bitwise xor. Computes `self ^ rhs`, returning `None` if the result ",
+                "is not a valid `", stringify!($struct), "`."),
+                #[inline]
+                pub fn checked_bitxor(self, rhs: $prim) -> Option<Self> {
+                    Self::new(self.get() ^ rhs)
+                }
+            }
+
+            doc_comment! {
+                concat!("Checked shift left. Computes `self << rhs`, returning `None` if `rhs` is ",
+                "larger than or equal to the number of bits in `self`, or if the result is not a ",
+                "valid `", stringify!($struct), "`."),
+                #[inline]
+                pub fn checked_shl(self, rhs: u32) -> Option<Self> {
+                    <$prim>::checked_shl(self.get(), rhs).and_then(Self::new)
+                }
+            }
+
+            doc_comment! {
+                concat!("Checked shift right. Computes `self >> rhs`, returning `None` if `rhs` is ",
+                "larger than or equal to the number of bits in `self`, or if the result is not a ",
+                "valid `", stringify!($struct), "`."),
+                #[inline]
+                pub fn checked_shr(self, rhs: u32) -> Option<Self> {
+                    <$prim>::checked_shr(self.get(), rhs).and_then(Self::new)
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_bitop {
+    ($struct:ident, $prim:ident, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $op:tt) => {
+        impl core::ops::$trait<$prim> for $struct {
+            type Output = Self;
+
+            #[inline]
+            fn $method(self, rhs: $prim) -> Self {
+                unsafe { Self::new_unchecked(self.get() $op rhs) }
+            }
+        }
+
+        impl core::ops::$trait for $struct {
+            type Output = Self;
+
+            #[inline]
+            fn $method(self, rhs: Self) -> Self {
+                <Self as core::ops::$trait<$prim>>::$method(self, rhs.get())
+            }
+        }
+
+        impl core::ops::$assign_trait<$prim> for $struct {
+            #[inline]
+            fn $assign_method(&mut self, rhs: $prim) {
+                *self = <Self as core::ops::$trait<$prim>>::$method(*self, rhs);
+            }
+        }
+
+        impl core::ops::$assign_trait for $struct {
+            #[inline]
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = <Self as core::ops::$trait<$prim>>::$method(*self, rhs.get());
+            }
+        }
+    };
+}
+
+macro_rules! impl_shift {
+    ($struct:ident, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $op:tt) => {
+        impl core::ops::$trait<u32> for $struct {
+            type Output = Self;
+
+            // Like the primitive, this panics in debug builds if `rhs` is too large. Otherwise,
+            // `rhs` is masked, which still results in a valid value.
+            #[inline]
+            #[track_caller]
+            fn $method(self, rhs: u32) -> Self {
+                unsafe { Self::new_unchecked(self.get() $op rhs) }
+            }
+        }
+
+        impl core::ops::$assign_trait<u32> for $struct {
+            #[inline]
+            #[track_caller]
+            fn $assign_method(&mut self, rhs: u32) {
+                *self = <Self as core::ops::$trait<u32>>::$method(*self, rhs);
+            }
+        }
+    };
+}
+
+macro_rules! impl_nonmaxu_bits {
+    ($struct:ident, $prim:ident) => {
+        impl_bitop!($struct, $prim, BitAnd, bitand, BitAndAssign, bitand_assign, &);
+        impl_shift!($struct, Shl, shl, ShlAssign, shl_assign, <<);
+        impl_shift!($struct, Shr, shr, ShrAssign, shr_assign, >>);
+        impl_fallible_ilog!($struct);
+
+        impl $struct {
+            /// Returns the number of ones in the binary representation of `self`.
+            #[inline]
+            pub fn count_ones(self) -> u32 {
+                self.get().count_ones()
+            }
+
+            /// Returns the number of zeros in the binary representation of `self`. Since `self`
+            /// is not all ones, this is never zero.
+            #[inline]
+            pub fn count_zeros(self) -> NonZeroU32 {
+                unsafe { NonZeroU32::new_unchecked(self.get().count_zeros()) }
+            }
+
+            /// Returns `true` if and only if `self == 2^k` for some `k`.
+            #[inline]
+            pub fn is_power_of_two(self) -> bool {
+                self.get().is_power_of_two()
+            }
+        }
+    };
+}
+
+impl_nonmaxu_bits!(NonMaxU8, u8);
+impl_nonmaxu_bits!(NonMaxU16, u16);
+impl_nonmaxu_bits!(NonMaxU32, u32);
+impl_nonmaxu_bits!(NonMaxU64, u64);
+impl_nonmaxu_bits!(NonMaxU128, u128);
+impl_nonmaxu_bits!(NonMaxUsize, usize);
+
+macro_rules! impl_nonminu_bits {
+    ($struct:ident, $prim:ident) => {
+        impl_bitop!($struct, $prim, BitOr, bitor, BitOrAssign, bitor_assign, |);
+
+        impl $struct {
+            /// Returns the number of ones in the binary representation of `self`. Since `self` is
+            /// not zero, this is never zero.
+            #[inline]
+            pub fn count_ones(self) -> NonZeroU32 {
+                unsafe { NonZeroU32::new_unchecked(self.get().count_ones()) }
+            }
+
+            /// Returns the number of zeros in the binary representation of `self`.
+            #[inline]
+            pub fn count_zeros(self) -> u32 {
+                self.get().count_zeros()
+            }
+
+            /// Returns `true` if and only if `self == 2^k` for some `k`.
+            #[inline]
+            pub fn is_power_of_two(self) -> bool {
+                self.get().is_power_of_two()
+            }
+
+            /// Returns the logarithm of `self` rounded down with base 2. Since `self` is not zero,
+            /// this can never panic.
+            #[inline]
+            pub fn ilog2(self) -> u32 {
+                self.as_nonzero_repr().ilog2()
+            }
+
+            /// Returns the logarithm of `self` rounded down with base 10. Since `self` is not
+            /// zero, this can never panic.
+            #[inline]
+            pub fn ilog10(self) -> u32 {
+                self.as_nonzero_repr().ilog10()
+            }
+        }
+    };
+}
+
+macro_rules! impl_signed_bit_counts {
+    ($struct:ident) => {
+        impl_fallible_ilog!($struct);
+
+        impl $struct {
+            /// Returns the number of ones in the binary representation of `self`.
+            #[inline]
+            pub fn count_ones(self) -> u32 {
+                self.get().count_ones()
+            }
+
+            /// Returns the number of zeros in the binary representation of `self`.
+            #[inline]
+            pub fn count_zeros(self) -> u32 {
+                self.get().count_zeros()
+            }
+
+            /// Returns `true` if and only if `self == 2^k` for some `k`, which implies that `self`
+            /// is positive.
+            #[inline]
+            pub fn is_power_of_two(self) -> bool {
+                self.get() > 0 && self.get().count_ones() == 1
+            }
+        }
+    };
+}
+
+impl_nonminu_bits!(NonMinU8, u8);
+impl_nonminu_bits!(NonMinU16, u16);
+impl_nonminu_bits!(NonMinU32, u32);
+impl_nonminu_bits!(NonMinU64, u64);
+impl_nonminu_bits!(NonMinU128, u128);
+impl_nonminu_bits!(NonMinUsize, usize);
+
+macro_rules! impl_nonmaxi_bits {
+    ($struct:ident) => {
+        impl_shift!($struct, Shl, shl, ShlAssign, shl_assign, <<);
+        impl_shift!($struct, Shr, shr, ShrAssign, shr_assign, >>);
+        impl_signed_bit_counts!($struct);
+    };
+}
+
+impl_nonmaxi_bits!(NonMaxI8);
+impl_nonmaxi_bits!(NonMaxI16);
+impl_nonmaxi_bits!(NonMaxI32);
+impl_nonmaxi_bits!(NonMaxI64);
+impl_nonmaxi_bits!(NonMaxI128);
+impl_nonmaxi_bits!(NonMaxIsize);
+
+macro_rules! impl_nonmini_bits {
+    ($struct:ident) => {
+        impl_shift!($struct, Shr, shr, ShrAssign, shr_assign, >>);
+        impl_signed_bit_counts!($struct);
+    };
+}
+
+impl_nonmini_bits!(NonMinI8);
+impl_nonmini_bits!(NonMinI16);
+impl_nonmini_bits!(NonMinI32);
+impl_nonmini_bits!(NonMinI64);
+impl_nonmini_bits!(NonMinI128);
+impl_nonmini_bits!(NonMinIsize);
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    macro_rules! test_checked_bits {
+        ($test_name:ident, $struct:ident, $prim:ident, $mask:expr) => {
+            #[test]
+            fn $test_name() {
+                let x = $struct::new(0b1010).unwrap();
+
+                assert_eq!(x.checked_bitand(0b0110).map($struct::get), Some(0b0010));
+                assert_eq!(x.checked_bitor(0b0110).map($struct::get), Some(0b1110));
+                assert_eq!(x.checked_bitxor(0b0110).map($struct::get), Some(0b1100));
+                assert_eq!(x.checked_shl(2).map($struct::get), Some(0b101000));
+                assert_eq!(x.checked_shr(2).map($struct::get), Some(0b10));
+                assert_eq!(x.checked_shl(<$prim>::BITS), None);
+                assert_eq!(x.checked_shr(<$prim>::BITS), None);
+
+                // results equal to the mask are rejected.
+                assert_eq!(x.checked_bitxor(x.get() ^ $mask), None);
+            }
+        };
+    }
+
+    test_checked_bits!(test_nonmaxu8, NonMaxU8, u8, u8::MAX);
+    test_checked_bits!(test_nonmaxu16, NonMaxU16, u16, u16::MAX);
+    test_checked_bits!(test_nonmaxu32, NonMaxU32, u32, u32::MAX);
+    test_checked_bits!(test_nonmaxu64, NonMaxU64, u64, u64::MAX);
+    test_checked_bits!(test_nonmaxu128, NonMaxU128, u128, u128::MAX);
+    test_checked_bits!(test_nonmaxusize, NonMaxUsize, usize, usize::MAX);
+
+    test_checked_bits!(test_nonmaxi8, NonMaxI8, i8, i8::MAX);
+    test_checked_bits!(test_nonmaxi16, NonMaxI16, i16, i16::MAX);
+    test_checked_bits!(test_nonmaxi32, NonMaxI32, i32, i32::MAX);
+    test_checked_bits!(test_nonmaxi64, NonMaxI64, i64, i64::MAX);
+    test_checked_bits!(test_nonmaxi128, NonMaxI128, i128, i128::MAX);
+    test_checked_bits!(test_nonmaxisize, NonMaxIsize, isize, isize::MAX);
+
+    test_checked_bits!(test_nonminu8, NonMinU8, u8, u8::MIN);
+    test_checked_bits!(test_nonminu16, NonMinU16, u16, u16::MIN);
+    test_checked_bits!(test_nonminu32, NonMinU32, u32, u32::MIN);
+    test_checked_bits!(test_nonminu64, NonMinU64, u64, u64::MIN);
+    test_checked_bits!(test_nonminu128, NonMinU128, u128, u128::MIN);
+    test_checked_bits!(test_nonminusize, NonMinUsize, usize, usize::MIN);
+
+    test_checked_bits!(test_nonmini8, NonMinI8, i8, i8::MIN);
+    test_checked_bits!(test_nonmini16, NonMinI16, i16, i16::MIN);
+    test_checked_bits!(test_nonmini32, NonMinI32, i32, i32::MIN);
+    test_checked_bits!(test_nonmini64, NonMinI64, i64, i64::MIN);
+    test_checked_bits!(test_nonmini128, NonMinI128, i128, i128::MIN);
+    test_checked_bits!(test_nonminisize, NonMinIsize, isize, isize::MIN);
+
+    #[test]
+    fn test_infallible_exhaustive() {
+        for a in 0..=u8::MAX {
+            for b in 0..=u8::MAX {
+                if let Some(x) = NonMaxU8::new(a) {
+                    assert_eq!((x & b).get(), a & b);
+                }
+
+                if let Some(x) = NonMinU8::new(a) {
+                    assert_eq!((x | b).get(), a | b);
+                }
+            }
+
+            for n in 0..8 {
+                if let Some(x) = NonMaxU8::new(a) {
+                    assert_eq!((x << n).get(), a << n);
+                    assert_eq!((x >> n).get(), a >> n);
+                }
+
+                if let Some(x) = NonMaxI8::new(a as i8) {
+                    assert_eq!((x << n).get(), (a as i8) << n);
+                    assert_eq!((x >> n).get(), (a as i8) >> n);
+                }
+
+                if let Some(x) = NonMinI8::new(a as i8) {
+                    assert_eq!((x >> n).get(), (a as i8) >> n);
+                }
+            }
+        }
+    }
+
+    macro_rules! test_bit_counts {
+        ($test_name:ident, $struct:ident, $prim:ident) => {
+            #[test]
+            fn $test_name() {
+                let x = $struct::new(0b101000).unwrap();
+                assert_eq!(x.leading_zeros(), <$prim>::BITS - 6);
+                assert_eq!(x.trailing_zeros(), 3);
+                assert_eq!(u32::from(x.count_ones()), 2);
+                assert_eq!(u32::from(x.count_zeros()), <$prim>::BITS - 2);
+                assert!(!x.is_power_of_two());
+                assert!($struct::new(64).unwrap().is_power_of_two());
+                assert_eq!(x.ilog2(), 5);
+                assert_eq!(x.ilog10(), 1);
+                assert_eq!(x.checked_ilog2(), Some(5));
+                assert_eq!(x.checked_ilog10(), Some(1));
+            }
+        };
+    }
+
+    test_bit_counts!(test_counts_nonmaxu8, NonMaxU8, u8);
+    test_bit_counts!(test_counts_nonmaxu16, NonMaxU16, u16);
+    test_bit_counts!(test_counts_nonmaxu32, NonMaxU32, u32);
+    test_bit_counts!(test_counts_nonmaxu64, NonMaxU64, u64);
+    test_bit_counts!(test_counts_nonmaxu128, NonMaxU128, u128);
+    test_bit_counts!(test_counts_nonmaxusize, NonMaxUsize, usize);
+
+    test_bit_counts!(test_counts_nonmaxi8, NonMaxI8, i8);
+    test_bit_counts!(test_counts_nonmaxi16, NonMaxI16, i16);
+    test_bit_counts!(test_counts_nonmaxi32, NonMaxI32, i32);
+    test_bit_counts!(test_counts_nonmaxi64, NonMaxI64, i64);
+    test_bit_counts!(test_counts_nonmaxi128, NonMaxI128, i128);
+    test_bit_counts!(test_counts_nonmaxisize, NonMaxIsize, isize);
+
+    test_bit_counts!(test_counts_nonminu8, NonMinU8, u8);
+    test_bit_counts!(test_counts_nonminu16, NonMinU16, u16);
+    test_bit_counts!(test_counts_nonminu32, NonMinU32, u32);
+    test_bit_counts!(test_counts_nonminu64, NonMinU64, u64);
+    test_bit_counts!(test_counts_nonminu128, NonMinU128, u128);
+    test_bit_counts!(test_counts_nonminusize, NonMinUsize, usize);
+
+    test_bit_counts!(test_counts_nonmini8, NonMinI8, i8);
+    test_bit_counts!(test_counts_nonmini16, NonMinI16, i16);
+    test_bit_counts!(test_counts_nonmini32, NonMinI32, i32);
+    test_bit_counts!(test_counts_nonmini64, NonMinI64, i64);
+    test_bit_counts!(test_counts_nonmini128, NonMinI128, i128);
+    test_bit_counts!(test_counts_nonminisize, NonMinIsize, isize);
+
+    #[test]
+    fn test_signed_bit_counts() {
+        let x = NonMaxI32::new(-64).unwrap();
+        assert!(!x.is_power_of_two());
+        assert_eq!(x.checked_ilog2(), None);
+        assert_eq!(x.count_ones(), 26);
+        assert_eq!(NonMinI8::new(i8::MIN + 1).unwrap().count_zeros(), 6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ilog2_zero_panics() {
+        NonMaxU32::new(0).unwrap().ilog2();
+    }
+
+    #[test]
+    fn test_assign() {
+        let mut x = NonMaxU32::new(0xff).unwrap();
+        x &= 0x0f;
+        x <<= 4;
+        x >>= 1;
+        assert_eq!(x.get(), 0x78);
+
+        let mut y = NonMinU32::new(0x10).unwrap();
+        y |= NonMinU32::new(0x01).unwrap();
+        assert_eq!(y.get(), 0x11);
+    }
+}
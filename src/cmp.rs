@@ -0,0 +1,138 @@
+//! Comparisons against primitives which preserve the invariant.
+//!
+//! The minimum of a `NonMaxX` and any primitive can never be `MAX`, and the maximum of a `NonMinX`
+//! and any primitive can never be `MIN`.
+
+use crate::{NonMaxI128, NonMaxI16, NonMaxI32, NonMaxI64, NonMaxI8, NonMaxIsize};
+use crate::{NonMaxU128, NonMaxU16, NonMaxU32, NonMaxU64, NonMaxU8, NonMaxUsize};
+use crate::{NonMinI128, NonMinI16, NonMinI32, NonMinI64, NonMinI8, NonMinIsize};
+use crate::{NonMinU128, NonMinU16, NonMinU32, NonMinU64, NonMinU8, NonMinUsize};
+
+macro_rules! impl_nonmax_cmp {
+    ($struct:ident, $prim:ident) => {
+        impl $struct {
+            /// Compares and returns the minimum of `self` and `other`. The result is never `MAX`.
+            #[inline]
+            pub fn min_prim(self, other: $prim) -> Self {
+                unsafe { Self::new_unchecked(core::cmp::min(self.get(), other)) }
+            }
+
+            /// Restricts `self` to the interval `[min, max]`. Since `max` is valid, the result is
+            /// never `MAX`.
+            ///
+            /// This is named to match `min_prim`/`max_prim`, and to avoid shadowing `Ord::clamp`,
+            /// which clamps between two values of `Self`.
+            ///
+            /// # Panics
+            /// Panics if `min > max`.
+            #[inline]
+            #[track_caller]
+            pub fn clamp_prim(self, min: $prim, max: Self) -> Self {
+                assert!(min <= max.get(), "`min` is greater than `max`");
+                unsafe { Self::new_unchecked(self.get().clamp(min, max.get())) }
+            }
+        }
+    };
+}
+
+impl_nonmax_cmp!(NonMaxU8, u8);
+impl_nonmax_cmp!(NonMaxU16, u16);
+impl_nonmax_cmp!(NonMaxU32, u32);
+impl_nonmax_cmp!(NonMaxU64, u64);
+impl_nonmax_cmp!(NonMaxU128, u128);
+impl_nonmax_cmp!(NonMaxUsize, usize);
+
+impl_nonmax_cmp!(NonMaxI8, i8);
+impl_nonmax_cmp!(NonMaxI16, i16);
+impl_nonmax_cmp!(NonMaxI32, i32);
+impl_nonmax_cmp!(NonMaxI64, i64);
+impl_nonmax_cmp!(NonMaxI128, i128);
+impl_nonmax_cmp!(NonMaxIsize, isize);
+
+macro_rules! impl_nonmin_cmp {
+    ($struct:ident, $prim:ident) => {
+        impl $struct {
+            /// Compares and returns the maximum of `self` and `other`. The result is never `MIN`.
+            #[inline]
+            pub fn max_prim(self, other: $prim) -> Self {
+                unsafe { Self::new_unchecked(core::cmp::max(self.get(), other)) }
+            }
+
+            /// Restricts `self` to the interval `[min, max]`. Since `min` is valid, the result is
+            /// never `MIN`.
+            ///
+            /// This is named to match `min_prim`/`max_prim`, and to avoid shadowing `Ord::clamp`,
+            /// which clamps between two values of `Self`.
+            ///
+            /// # Panics
+            /// Panics if `min > max`.
+            #[inline]
+            #[track_caller]
+            pub fn clamp_prim(self, min: Self, max: $prim) -> Self {
+                assert!(min.get() <= max, "`min` is greater than `max`");
+                unsafe { Self::new_unchecked(self.get().clamp(min.get(), max)) }
+            }
+        }
+    };
+}
+
+impl_nonmin_cmp!(NonMinU8, u8);
+impl_nonmin_cmp!(NonMinU16, u16);
+impl_nonmin_cmp!(NonMinU32, u32);
+impl_nonmin_cmp!(NonMinU64, u64);
+impl_nonmin_cmp!(NonMinU128, u128);
+impl_nonmin_cmp!(NonMinUsize, usize);
+
+impl_nonmin_cmp!(NonMinI8, i8);
+impl_nonmin_cmp!(NonMinI16, i16);
+impl_nonmin_cmp!(NonMinI32, i32);
+impl_nonmin_cmp!(NonMinI64, i64);
+impl_nonmin_cmp!(NonMinI128, i128);
+impl_nonmin_cmp!(NonMinIsize, isize);
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    macro_rules! test_cmp {
+        ($test_name:ident, $nonmax:ident, $nonmin:ident, $prim:ident) => {
+            #[test]
+            fn $test_name() {
+                let x = $nonmax::new(42).unwrap();
+                assert_eq!(x.min_prim(<$prim>::MAX).get(), 42);
+                assert_eq!(x.min_prim(7).get(), 7);
+                assert_eq!(x.clamp_prim(50, $nonmax::new(60).unwrap()).get(), 50);
+                assert_eq!(x.clamp_prim(0, $nonmax::new(10).unwrap()).get(), 10);
+                assert_eq!($nonmax::clamp_new(<$prim>::MAX).get(), <$prim>::MAX - 1);
+
+                let y = $nonmin::new(42).unwrap();
+                assert_eq!(y.max_prim(<$prim>::MIN).get(), 42);
+                assert_eq!(y.max_prim(99).get(), 99);
+                assert_eq!(y.clamp_prim($nonmin::new(50).unwrap(), 60).get(), 50);
+                assert_eq!(y.clamp_prim($nonmin::new(1).unwrap(), 10).get(), 10);
+                assert_eq!($nonmin::clamp_new(<$prim>::MIN).get(), <$prim>::MIN + 1);
+            }
+        };
+    }
+
+    test_cmp!(test_u8, NonMaxU8, NonMinU8, u8);
+    test_cmp!(test_u16, NonMaxU16, NonMinU16, u16);
+    test_cmp!(test_u32, NonMaxU32, NonMinU32, u32);
+    test_cmp!(test_u64, NonMaxU64, NonMinU64, u64);
+    test_cmp!(test_u128, NonMaxU128, NonMinU128, u128);
+    test_cmp!(test_usize, NonMaxUsize, NonMinUsize, usize);
+
+    test_cmp!(test_i8, NonMaxI8, NonMinI8, i8);
+    test_cmp!(test_i16, NonMaxI16, NonMinI16, i16);
+    test_cmp!(test_i32, NonMaxI32, NonMinI32, i32);
+    test_cmp!(test_i64, NonMaxI64, NonMinI64, i64);
+    test_cmp!(test_i128, NonMaxI128, NonMinI128, i128);
+    test_cmp!(test_isize, NonMaxIsize, NonMinIsize, isize);
+
+    #[test]
+    #[should_panic(expected = "`min` is greater than `max`")]
+    fn test_clamp_invalid_interval() {
+        let x = NonMaxU8::new(42).unwrap();
+        x.clamp_prim(11, NonMaxU8::new(10).unwrap());
+    }
+}
@@ -0,0 +1,102 @@
+//! Bitwise complement, which maps `NonMaxX` to `NonMinX` and vice versa.
+//!
+//! The bitwise complement of `MAX` is `MIN` for both signed and unsigned integers. Hence, the
+//! complement of a value which is not `MAX` is never `MIN`, and the other way around.
+
+use crate::{NonMaxI128, NonMaxI16, NonMaxI32, NonMaxI64, NonMaxI8, NonMaxIsize};
+use crate::{NonMaxU128, NonMaxU16, NonMaxU32, NonMaxU64, NonMaxU8, NonMaxUsize};
+use crate::{NonMinI128, NonMinI16, NonMinI32, NonMinI64, NonMinI8, NonMinIsize};
+use crate::{NonMinU128, NonMinU16, NonMinU32, NonMinU64, NonMinU8, NonMinUsize};
+
+macro_rules! impl_complement {
+    ($struct:ident, $other:ident) => {
+        impl $struct {
+            doc_comment! {
+                concat!("Returns the bitwise complement `!self` as a `", stringify!($other), "`."),
+                #[inline]
+                pub fn complement(self) -> $other {
+                    // Without the `nightly` feature, `!x ^ !mask == x ^ mask`, so the inner value
+                    // stays the same and this compiles to a no-op.
+                    unsafe { $other::new_unchecked(!self.get()) }
+                }
+            }
+        }
+
+        impl core::ops::Not for $struct {
+            type Output = $other;
+
+            #[inline]
+            fn not(self) -> $other {
+                self.complement()
+            }
+        }
+    };
+}
+
+impl_complement!(NonMaxU8, NonMinU8);
+impl_complement!(NonMaxU16, NonMinU16);
+impl_complement!(NonMaxU32, NonMinU32);
+impl_complement!(NonMaxU64, NonMinU64);
+impl_complement!(NonMaxU128, NonMinU128);
+impl_complement!(NonMaxUsize, NonMinUsize);
+
+impl_complement!(NonMaxI8, NonMinI8);
+impl_complement!(NonMaxI16, NonMinI16);
+impl_complement!(NonMaxI32, NonMinI32);
+impl_complement!(NonMaxI64, NonMinI64);
+impl_complement!(NonMaxI128, NonMinI128);
+impl_complement!(NonMaxIsize, NonMinIsize);
+
+impl_complement!(NonMinU8, NonMaxU8);
+impl_complement!(NonMinU16, NonMaxU16);
+impl_complement!(NonMinU32, NonMaxU32);
+impl_complement!(NonMinU64, NonMaxU64);
+impl_complement!(NonMinU128, NonMaxU128);
+impl_complement!(NonMinUsize, NonMaxUsize);
+
+impl_complement!(NonMinI8, NonMaxI8);
+impl_complement!(NonMinI16, NonMaxI16);
+impl_complement!(NonMinI32, NonMaxI32);
+impl_complement!(NonMinI64, NonMaxI64);
+impl_complement!(NonMinI128, NonMaxI128);
+impl_complement!(NonMinIsize, NonMaxIsize);
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    macro_rules! test_complement {
+        ($test_name:ident, $nonmax:ident, $nonmin:ident, $prim:ident) => {
+            #[test]
+            fn $test_name() {
+                for &value in &[0, 1, 42, <$prim>::MAX - 1, <$prim>::MIN] {
+                    let x = $nonmax::new(value).unwrap();
+                    assert_eq!((!x).get(), !value);
+                    assert_eq!(x.complement(), !x);
+                    assert_eq!(!!x, x);
+                }
+
+                for &value in &[1, 42, <$prim>::MAX, <$prim>::MIN + 1] {
+                    let x = $nonmin::new(value).unwrap();
+                    assert_eq!((!x).get(), !value);
+                    assert_eq!(x.complement(), !x);
+                    assert_eq!(!!x, x);
+                }
+            }
+        };
+    }
+
+    test_complement!(test_u8, NonMaxU8, NonMinU8, u8);
+    test_complement!(test_u16, NonMaxU16, NonMinU16, u16);
+    test_complement!(test_u32, NonMaxU32, NonMinU32, u32);
+    test_complement!(test_u64, NonMaxU64, NonMinU64, u64);
+    test_complement!(test_u128, NonMaxU128, NonMinU128, u128);
+    test_complement!(test_usize, NonMaxUsize, NonMinUsize, usize);
+
+    test_complement!(test_i8, NonMaxI8, NonMinI8, i8);
+    test_complement!(test_i16, NonMaxI16, NonMinI16, i16);
+    test_complement!(test_i32, NonMaxI32, NonMinI32, i32);
+    test_complement!(test_i64, NonMaxI64, NonMinI64, i64);
+    test_complement!(test_i128, NonMaxI128, NonMinI128, i128);
+    test_complement!(test_isize, NonMaxIsize, NonMinIsize, isize);
+}
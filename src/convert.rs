@@ -0,0 +1,314 @@
+//! Conversions between the different widths and signedness of the types and the primitives.
+//!
+//! A conversion is implemented as `From` if every valid value of the source type is a valid value
+//! of the target type on every supported pointer width, and as `TryFrom` otherwise. For example,
+//! `NonMaxU8` converts losslessly into `NonMaxU16` and `i16`, while a `NonMaxU16` converts into a
+//! `NonMaxU8` only if it fits.
+//!
+//! Like the standard library, `usize` and `isize` are assumed to be at least 16 bits wide, without
+//! an upper bound. So the 8-bit types convert losslessly into `NonMaxUsize`, the 16-bit types only
+//! if they exclude the same bound, and the `usize`/`isize` types never convert losslessly into
+//! another type.
+//!
+//! Rather than listing every pair of types, the conversions are generated from this rule by the
+//! `impl_conversions!` macro, which is given the primitives ordered by width.
+
+use crate::error::NonMinMaxError;
+use crate::{NonMax, NonMin};
+use core::convert::TryFrom;
+
+/// Implements `From<$src> for $dst`, where every valid value of `$src` is a valid value of `$dst`.
+macro_rules! impl_from {
+    ($src:ty: $src_prim:ident => $dst_prim:ident) => {
+        impl From<$src> for $dst_prim {
+            #[inline]
+            fn from(value: $src) -> Self {
+                <$src_prim>::from(value) as $dst_prim
+            }
+        }
+    };
+    ($src:ty: $src_prim:ident => $dst:ident<$dst_prim:ident>) => {
+        impl From<$src> for $dst<$dst_prim> {
+            #[inline]
+            fn from(value: $src) -> Self {
+                // Every valid value of the source is a valid value of the target.
+                unsafe { $dst::new_unchecked(<$src_prim>::from(value) as $dst_prim) }
+            }
+        }
+    };
+}
+
+/// Implements `TryFrom<$src> for $dst`, which fails if the value is out of range or if it is the
+/// excluded value of `$dst`.
+macro_rules! impl_try_from {
+    ($src:ty: $src_prim:ident => $dst_prim:ident) => {
+        impl TryFrom<$src> for $dst_prim {
+            type Error = NonMinMaxError;
+
+            #[inline]
+            fn try_from(value: $src) -> Result<Self, Self::Error> {
+                let value = <$src_prim>::from(value);
+                <$dst_prim>::try_from(value).map_err(|_| NonMinMaxError::overflow(value))
+            }
+        }
+    };
+    ($src:ty: $src_prim:ident => $dst:ident<$dst_prim:ident>) => {
+        impl TryFrom<$src> for $dst<$dst_prim> {
+            type Error = NonMinMaxError;
+
+            #[inline]
+            fn try_from(value: $src) -> Result<Self, Self::Error> {
+                let value = <$src_prim>::from(value);
+                let value =
+                    <$dst_prim>::try_from(value).map_err(|_| NonMinMaxError::overflow(value))?;
+                Self::try_from(value)
+            }
+        }
+    };
+}
+
+/// Implements the conversions between `NonMaxX` and `NonMinX` of the same primitive. The
+/// conversions to and from the primitive itself are implemented alongside the types.
+macro_rules! impl_same {
+    ($prim:ident) => {
+        impl_try_from!(NonMax<$prim>: $prim => NonMin<$prim>);
+        impl_try_from!(NonMin<$prim>: $prim => NonMax<$prim>);
+    };
+}
+
+/// Implements the conversions from the types of `$src` into the types of `$dst`, where `$dst` can
+/// represent every value of `$src`.
+///
+/// `$hi` and `$lo` tell whether the upper and lower bound of `$dst` lie `strict`ly beyond those of
+/// `$src`, or whether they may be `equal`. In the latter case, only the source type which excludes
+/// the same bound converts losslessly into the target type which excludes it.
+macro_rules! impl_widening {
+    ($src:ident => $dst:ident, $hi:ident, $lo:ident) => {
+        impl_from!(NonMax<$src>: $src => $dst);
+        impl_from!(NonMin<$src>: $src => $dst);
+        impl_widening!(@bound NonMax, $hi, $src => $dst);
+        impl_widening!(@bound NonMin, $lo, $src => $dst);
+    };
+    (@bound $bound:ident, strict, $src:ident => $dst:ident) => {
+        impl_from!($src: $src => $bound<$dst>);
+        impl_from!(NonMax<$src>: $src => $bound<$dst>);
+        impl_from!(NonMin<$src>: $src => $bound<$dst>);
+    };
+    (@bound NonMax, equal, $src:ident => $dst:ident) => {
+        impl_try_from!($src: $src => NonMax<$dst>);
+        impl_from!(NonMax<$src>: $src => NonMax<$dst>);
+        impl_try_from!(NonMin<$src>: $src => NonMax<$dst>);
+    };
+    (@bound NonMin, equal, $src:ident => $dst:ident) => {
+        impl_try_from!($src: $src => NonMin<$dst>);
+        impl_try_from!(NonMax<$src>: $src => NonMin<$dst>);
+        impl_from!(NonMin<$src>: $src => NonMin<$dst>);
+    };
+}
+
+/// Implements the conversions from the types of `$src` into the types of `$dst`, where `$dst`
+/// cannot represent every value of `$src` on some pointer width.
+macro_rules! impl_narrowing {
+    ($src:ident => $dst:ident) => {
+        impl_try_from!(NonMax<$src>: $src => $dst);
+        impl_try_from!(NonMin<$src>: $src => $dst);
+        impl_narrowing!(@bound NonMax, $src => $dst);
+        impl_narrowing!(@bound NonMin, $src => $dst);
+    };
+    (@bound $bound:ident, $src:ident => $dst:ident) => {
+        impl_try_from!($src: $src => $bound<$dst>);
+        impl_try_from!(NonMax<$src>: $src => $bound<$dst>);
+        impl_try_from!(NonMin<$src>: $src => $bound<$dst>);
+    };
+}
+
+/// Implements the conversions between every pair of the given `[unsigned, signed]` primitives,
+/// which are ordered by width.
+///
+/// A type converts losslessly into a wider type, unless the source is signed and the target is
+/// unsigned. A wider unsigned type only has a higher upper bound, while a wider signed type has a
+/// lower lower bound as well.
+///
+/// The `pointer` arm places the `usize`/`isize` group. It is wider than the `narrower` groups, may
+/// have the same bounds as the `equal` group, and may be arbitrarily wide. So it never converts
+/// losslessly into another group, and the `wider` groups never convert losslessly into it.
+macro_rules! impl_conversions {
+    ([$([$nu:ident, $ni:ident]),*]) => {};
+    ([$([$nu:ident, $ni:ident]),*] [$u:ident, $i:ident] $($rest:tt)*) => {
+        impl_conversions!(@group $u, $i);
+        $(
+            impl_widening!($nu => $u, strict, equal);
+            impl_widening!($nu => $i, strict, strict);
+            impl_widening!($ni => $i, strict, strict);
+            impl_narrowing!($ni => $u);
+            impl_conversions!(@narrowing [$u, $i] => [$nu, $ni]);
+        )*
+        impl_conversions!([$([$nu, $ni],)* [$u, $i]] $($rest)*);
+    };
+    (
+        pointer [$u:ident, $i:ident],
+        narrower [$([$nu:ident, $ni:ident]),*],
+        equal [$eu:ident, $ei:ident],
+        wider [$([$wu:ident, $wi:ident]),*]
+    ) => {
+        impl_conversions!(@group $u, $i);
+        $(
+            impl_widening!($nu => $u, strict, equal);
+            impl_widening!($nu => $i, strict, strict);
+            impl_widening!($ni => $i, strict, strict);
+            impl_narrowing!($ni => $u);
+            impl_conversions!(@narrowing [$u, $i] => [$nu, $ni]);
+        )*
+        impl_widening!($eu => $u, equal, equal);
+        impl_narrowing!($eu => $i);
+        impl_widening!($ei => $i, equal, equal);
+        impl_narrowing!($ei => $u);
+        impl_conversions!(@narrowing [$u, $i] => [$eu, $ei]);
+        $(
+            impl_conversions!(@narrowing [$u, $i] => [$wu, $wi]);
+            impl_conversions!(@narrowing [$wu, $wi] => [$u, $i]);
+        )*
+    };
+    (@group $u:ident, $i:ident) => {
+        impl_same!($u);
+        impl_same!($i);
+        impl_narrowing!($u => $i);
+        impl_narrowing!($i => $u);
+    };
+    (@narrowing [$su:ident, $si:ident] => [$du:ident, $di:ident]) => {
+        impl_narrowing!($su => $du);
+        impl_narrowing!($su => $di);
+        impl_narrowing!($si => $du);
+        impl_narrowing!($si => $di);
+    };
+}
+
+impl_conversions!([] [u8, i8] [u16, i16] [u32, i32] [u64, i64] [u128, i128]);
+impl_conversions!(
+    pointer [usize, isize],
+    narrower [[u8, i8]],
+    equal [u16, i16],
+    wider [[u32, i32], [u64, i64], [u128, i128]]
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn test_from() {
+        let x = NonMaxU8::new(254).unwrap();
+        assert_eq!(NonMaxU16::from(x).get(), 254);
+        assert_eq!(NonMaxI16::from(x).get(), 254);
+        assert_eq!(NonMinI16::from(x).get(), 254);
+        assert_eq!(i16::from(x), 254);
+        assert_eq!(NonMaxU16::from(255u8).get(), 255);
+        assert_eq!(NonMinI16::from(-128i8).get(), -128);
+
+        let x = NonMinI16::new(i16::MIN + 1).unwrap();
+        assert_eq!(NonMinI32::from(x).get(), i32::from(i16::MIN + 1));
+        assert_eq!(NonMaxI32::from(x).get(), i32::from(i16::MIN + 1));
+
+        let x = NonMaxU32::new(u32::MAX - 1).unwrap();
+        assert_eq!(u64::from(x), u64::from(u32::MAX - 1));
+        assert_eq!(i64::from(x), i64::from(u32::MAX - 1));
+        assert_eq!(NonMaxI64::from(x).get(), i64::from(u32::MAX - 1));
+
+        let x = NonMaxU16::new(1000).unwrap();
+        assert_eq!(NonMaxI32::from(x).get(), 1000);
+        assert_eq!(NonMaxUsize::from(x).get(), 1000);
+    }
+
+    #[test]
+    fn test_try_from() {
+        let x = NonMaxU64::new(42).unwrap();
+        assert_eq!(NonMaxU32::try_from(x).unwrap().get(), 42);
+        assert_eq!(NonMinU8::try_from(x).unwrap().get(), 42);
+        assert_eq!(u8::try_from(x).unwrap(), 42);
+
+        let x = NonMaxU64::new(u64::from(u32::MAX)).unwrap();
+        let err = NonMaxU32::try_from(x).unwrap_err();
+        assert_eq!(err.kind(), &NonMinMaxErrorKind::Max);
+        let err = NonMaxU16::try_from(x).unwrap_err();
+        assert_eq!(err.kind(), &NonMinMaxErrorKind::PosOverflow);
+        let err = u16::try_from(x).unwrap_err();
+        assert_eq!(err.kind(), &NonMinMaxErrorKind::PosOverflow);
+
+        let x = NonMaxI32::new(-1).unwrap();
+        let err = NonMaxU32::try_from(x).unwrap_err();
+        assert_eq!(err.kind(), &NonMinMaxErrorKind::NegOverflow);
+        let err = u64::try_from(x).unwrap_err();
+        assert_eq!(err.kind(), &NonMinMaxErrorKind::NegOverflow);
+        assert_eq!(
+            NonMaxU32::try_from(NonMaxI32::new(7).unwrap())
+                .unwrap()
+                .get(),
+            7
+        );
+
+        let x = NonMaxI32::new(i32::MIN).unwrap();
+        let err = NonMinI32::try_from(x).unwrap_err();
+        assert_eq!(err.kind(), &NonMinMaxErrorKind::Min);
+        assert_eq!(
+            NonMinI32::try_from(NonMaxI32::new(i32::MIN + 1).unwrap())
+                .unwrap()
+                .get(),
+            i32::MIN + 1
+        );
+
+        let err = NonMaxU8::try_from(-1i8).unwrap_err();
+        assert_eq!(err.kind(), &NonMinMaxErrorKind::NegOverflow);
+        let err = NonMaxU8::try_from(255u16).unwrap_err();
+        assert_eq!(err.kind(), &NonMinMaxErrorKind::Max);
+        assert_eq!(NonMinU8::try_from(200u64).unwrap().get(), 200);
+    }
+
+    #[test]
+    fn test_pointer_width() {
+        // The 8-bit types fit into `usize`/`isize` on every pointer width.
+        assert_eq!(NonMaxUsize::from(NonMaxU8::MAX).get(), 254);
+        assert_eq!(NonMinUsize::from(NonMinU8::MIN).get(), 1);
+        assert_eq!(NonMaxUsize::from(u8::MAX).get(), 255);
+        assert_eq!(NonMinIsize::from(NonMaxI8::MIN).get(), -128);
+        assert_eq!(NonMaxIsize::from(i8::MAX).get(), 127);
+
+        // The 16-bit types fit only if they exclude the same bound.
+        assert_eq!(NonMaxUsize::from(NonMaxU16::MAX).get(), 65534);
+        assert_eq!(NonMinIsize::from(NonMinI16::MIN).get(), -32767);
+        assert_eq!(NonMaxUsize::try_from(u16::MAX).unwrap().get(), 65535);
+        assert_eq!(NonMinIsize::try_from(i16::MIN).unwrap().get(), -32768);
+        assert_eq!(NonMaxIsize::try_from(NonMinI16::MAX).unwrap().get(), 32767);
+
+        // The wider types may not fit.
+        let err = NonMaxUsize::try_from(NonMaxI32::new(-1).unwrap()).unwrap_err();
+        assert_eq!(err.kind(), &NonMinMaxErrorKind::NegOverflow);
+        let err = NonMaxUsize::try_from(u128::MAX).unwrap_err();
+        assert_eq!(err.kind(), &NonMinMaxErrorKind::PosOverflow);
+        let err = NonMinIsize::try_from(NonMinI128::MIN).unwrap_err();
+        assert_eq!(err.kind(), &NonMinMaxErrorKind::NegOverflow);
+
+        // Like the standard library, no type converts losslessly out of `usize`/`isize`.
+        let x = NonMaxUsize::new(7).unwrap();
+        assert_eq!(u128::try_from(x).unwrap(), 7);
+        assert_eq!(NonMaxU128::try_from(x).unwrap().get(), 7);
+        assert_eq!(NonMinI128::try_from(x).unwrap().get(), 7);
+        let x = NonMinUsize::MAX;
+        assert_eq!(NonMaxU128::try_from(x).unwrap().get(), usize::MAX as u128);
+        let err = NonMaxUsize::try_from(x).unwrap_err();
+        assert_eq!(err.kind(), &NonMinMaxErrorKind::Max);
+        let err = NonMaxU16::try_from(x).unwrap_err();
+        assert_eq!(err.kind(), &NonMinMaxErrorKind::PosOverflow);
+
+        let x = NonMaxIsize::MIN;
+        assert_eq!(NonMinI128::try_from(x).unwrap().get(), isize::MIN as i128);
+        let err = NonMinIsize::try_from(x).unwrap_err();
+        assert_eq!(err.kind(), &NonMinMaxErrorKind::Min);
+        let err = usize::try_from(x).unwrap_err();
+        assert_eq!(err.kind(), &NonMinMaxErrorKind::NegOverflow);
+        let x = NonMinIsize::new(-5).unwrap();
+        assert_eq!(i128::try_from(x).unwrap(), -5);
+        assert_eq!(NonMaxI128::try_from(x).unwrap().get(), -5);
+        assert_eq!(NonMaxI16::try_from(x).unwrap().get(), -5);
+    }
+}
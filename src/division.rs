@@ -0,0 +1,96 @@
+//! Division by `NonMinUX` values, which are never zero.
+//!
+//! Like `core::num::NonZeroUX`, a `NonMinUX` can be used as the divisor of its primitive type.
+//! These divisions can never panic since the divisor is known to be non-zero.
+
+use crate::{NonMinU128, NonMinU16, NonMinU32, NonMinU64, NonMinU8, NonMinUsize};
+
+macro_rules! impl_division {
+    ($struct:ident, $prim:ident) => {
+        impl $struct {
+            /// Calculates the quotient of `self` and `rhs`, rounding the result towards positive
+            /// infinity. The result is at least one, so this can never fail.
+            #[inline]
+            pub fn div_ceil(self, rhs: Self) -> Self {
+                unsafe { Self::new_unchecked(self.get().div_ceil(rhs.get())) }
+            }
+        }
+
+        impl core::ops::Div<$struct> for $prim {
+            type Output = $prim;
+
+            /// This operation rounds towards zero, truncating any fractional part of the exact
+            /// result, and cannot panic.
+            #[inline]
+            fn div(self, rhs: $struct) -> $prim {
+                self / *rhs.as_nonzero_repr()
+            }
+        }
+
+        impl core::ops::Rem<$struct> for $prim {
+            type Output = $prim;
+
+            /// This operation satisfies `n % d == n - (n / d) * d`, and cannot panic.
+            #[inline]
+            fn rem(self, rhs: $struct) -> $prim {
+                self % *rhs.as_nonzero_repr()
+            }
+        }
+
+        impl core::ops::DivAssign<$struct> for $prim {
+            #[inline]
+            fn div_assign(&mut self, rhs: $struct) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl core::ops::RemAssign<$struct> for $prim {
+            #[inline]
+            fn rem_assign(&mut self, rhs: $struct) {
+                *self = *self % rhs;
+            }
+        }
+    };
+}
+
+impl_division!(NonMinU8, u8);
+impl_division!(NonMinU16, u16);
+impl_division!(NonMinU32, u32);
+impl_division!(NonMinU64, u64);
+impl_division!(NonMinU128, u128);
+impl_division!(NonMinUsize, usize);
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    macro_rules! test_division {
+        ($test_name:ident, $struct:ident, $prim:ident) => {
+            #[test]
+            fn $test_name() {
+                let d = $struct::new(7).unwrap();
+
+                assert_eq!(100 as $prim / d, 14);
+                assert_eq!(100 as $prim % d, 2);
+                assert_eq!(<$prim>::MAX / $struct::new(1).unwrap(), <$prim>::MAX);
+
+                let mut x = 100 as $prim;
+                x /= d;
+                assert_eq!(x, 14);
+                x %= d;
+                assert_eq!(x, 0);
+
+                assert_eq!($struct::new(100).unwrap().div_ceil(d).get(), 15);
+                assert_eq!($struct::new(98).unwrap().div_ceil(d).get(), 14);
+                assert_eq!($struct::new(1).unwrap().div_ceil(d).get(), 1);
+            }
+        };
+    }
+
+    test_division!(test_nonminu8, NonMinU8, u8);
+    test_division!(test_nonminu16, NonMinU16, u16);
+    test_division!(test_nonminu32, NonMinU32, u32);
+    test_division!(test_nonminu64, NonMinU64, u64);
+    test_division!(test_nonminu128, NonMinU128, u128);
+    test_division!(test_nonminusize, NonMinUsize, usize);
+}
@@ -0,0 +1,156 @@
+//! Bijections between the signed and unsigned types.
+//!
+//! Zigzag encoding maps `0, -1, 1, -2, 2, ...` to `0, 1, 2, 3, 4, ...`, so that small magnitudes
+//! become small unsigned values. It maps `MIN` to the unsigned `MAX`, which makes it a bijection
+//! between `NonMinIX` and `NonMaxUX`.
+//!
+//! Offset binary flips the sign bit, which maps `MIN` to zero and `MAX` to the unsigned `MAX`. It
+//! preserves order and is a bijection between `NonMinIX` and `NonMinUX`, as well as between
+//! `NonMaxIX` and `NonMaxUX`.
+
+use crate::{NonMaxI128, NonMaxI16, NonMaxI32, NonMaxI64, NonMaxI8, NonMaxIsize};
+use crate::{NonMaxU128, NonMaxU16, NonMaxU32, NonMaxU64, NonMaxU8, NonMaxUsize};
+use crate::{NonMinI128, NonMinI16, NonMinI32, NonMinI64, NonMinI8, NonMinIsize};
+use crate::{NonMinU128, NonMinU16, NonMinU32, NonMinU64, NonMinU8, NonMinUsize};
+
+macro_rules! impl_offset_binary {
+    ($struct:ident, $prim:ident, $unsigned_struct:ident, $unsigned:ident) => {
+        impl $struct {
+            doc_comment! {
+                concat!("Converts `self` to a `", stringify!($unsigned_struct), "` by flipping the ",
+                "sign bit (offset binary). This conversion preserves order."),
+                #[inline]
+                pub fn to_offset_binary(self) -> $unsigned_struct {
+                    let value = (self.get() as $unsigned) ^ (1 << (<$prim>::BITS - 1));
+                    unsafe { $unsigned_struct::new_unchecked(value) }
+                }
+            }
+
+            doc_comment! {
+                concat!("Converts a `", stringify!($unsigned_struct), "` in offset binary back to a `",
+                stringify!($struct), "`. This is the inverse of `to_offset_binary`."),
+                #[inline]
+                pub fn from_offset_binary(value: $unsigned_struct) -> Self {
+                    let value = (value.get() ^ (1 << (<$prim>::BITS - 1))) as $prim;
+                    unsafe { Self::new_unchecked(value) }
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_zigzag {
+    ($struct:ident, $prim:ident, $unsigned_struct:ident, $unsigned:ident) => {
+        impl $struct {
+            doc_comment! {
+                concat!("Converts `self` to a `", stringify!($unsigned_struct), "` using zigzag ",
+                "encoding, mapping `0, -1, 1, -2, 2, ...` to `0, 1, 2, 3, 4, ...`."),
+                #[inline]
+                pub fn zigzag_encode(self) -> $unsigned_struct {
+                    let x = self.get();
+                    let value = ((x << 1) ^ (x >> (<$prim>::BITS - 1))) as $unsigned;
+                    unsafe { $unsigned_struct::new_unchecked(value) }
+                }
+            }
+
+            doc_comment! {
+                concat!("Converts a zigzag-encoded `", stringify!($unsigned_struct), "` back to a `",
+                stringify!($struct), "`. This is the inverse of `zigzag_encode`."),
+                #[inline]
+                pub fn zigzag_decode(value: $unsigned_struct) -> Self {
+                    let x = value.get();
+                    let value = ((x >> 1) as $prim) ^ -((x & 1) as $prim);
+                    unsafe { Self::new_unchecked(value) }
+                }
+            }
+        }
+    };
+}
+
+impl_offset_binary!(NonMaxI8, i8, NonMaxU8, u8);
+impl_offset_binary!(NonMaxI16, i16, NonMaxU16, u16);
+impl_offset_binary!(NonMaxI32, i32, NonMaxU32, u32);
+impl_offset_binary!(NonMaxI64, i64, NonMaxU64, u64);
+impl_offset_binary!(NonMaxI128, i128, NonMaxU128, u128);
+impl_offset_binary!(NonMaxIsize, isize, NonMaxUsize, usize);
+
+impl_offset_binary!(NonMinI8, i8, NonMinU8, u8);
+impl_offset_binary!(NonMinI16, i16, NonMinU16, u16);
+impl_offset_binary!(NonMinI32, i32, NonMinU32, u32);
+impl_offset_binary!(NonMinI64, i64, NonMinU64, u64);
+impl_offset_binary!(NonMinI128, i128, NonMinU128, u128);
+impl_offset_binary!(NonMinIsize, isize, NonMinUsize, usize);
+
+impl_zigzag!(NonMinI8, i8, NonMaxU8, u8);
+impl_zigzag!(NonMinI16, i16, NonMaxU16, u16);
+impl_zigzag!(NonMinI32, i32, NonMaxU32, u32);
+impl_zigzag!(NonMinI64, i64, NonMaxU64, u64);
+impl_zigzag!(NonMinI128, i128, NonMaxU128, u128);
+impl_zigzag!(NonMinIsize, isize, NonMaxUsize, usize);
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    macro_rules! test_encoding {
+        ($test_name:ident, $nonmax:ident, $nonmin:ident, $prim:ident, $unsigned_nonmax:ident, $unsigned_nonmin:ident, $unsigned:ident) => {
+            #[test]
+            fn $test_name() {
+                let zigzag = |x: $prim| $nonmin::new(x).unwrap().zigzag_encode().get();
+                assert_eq!(zigzag(0), 0);
+                assert_eq!(zigzag(-1), 1);
+                assert_eq!(zigzag(1), 2);
+                assert_eq!(zigzag(-2), 3);
+                assert_eq!(zigzag(<$prim>::MAX), <$unsigned>::MAX - 1);
+                assert_eq!(zigzag(<$prim>::MIN + 1), <$unsigned>::MAX - 2);
+
+                for &x in &[0, 1, -1, 42, -42, <$prim>::MAX, <$prim>::MIN + 1] {
+                    let x = $nonmin::new(x).unwrap();
+                    assert_eq!($nonmin::zigzag_decode(x.zigzag_encode()), x);
+                    assert_eq!($nonmin::from_offset_binary(x.to_offset_binary()), x);
+                }
+
+                for &x in &[0, 1, -1, 42, -42, <$prim>::MAX - 1, <$prim>::MIN] {
+                    let x = $nonmax::new(x).unwrap();
+                    assert_eq!($nonmax::from_offset_binary(x.to_offset_binary()), x);
+                }
+
+                // offset binary preserves order.
+                let lo = $nonmax::new(<$prim>::MIN).unwrap().to_offset_binary();
+                let hi = $nonmax::new(<$prim>::MAX - 1).unwrap().to_offset_binary();
+                assert_eq!(lo, $unsigned_nonmax::new(0).unwrap());
+                assert_eq!(hi, $unsigned_nonmax::new(<$unsigned>::MAX - 1).unwrap());
+
+                let lo = $nonmin::new(<$prim>::MIN + 1).unwrap().to_offset_binary();
+                let hi = $nonmin::new(<$prim>::MAX).unwrap().to_offset_binary();
+                assert_eq!(lo, $unsigned_nonmin::new(1).unwrap());
+                assert_eq!(hi, $unsigned_nonmin::new(<$unsigned>::MAX).unwrap());
+            }
+        };
+    }
+
+    test_encoding!(test_8, NonMaxI8, NonMinI8, i8, NonMaxU8, NonMinU8, u8);
+    test_encoding!(test_16, NonMaxI16, NonMinI16, i16, NonMaxU16, NonMinU16, u16);
+    test_encoding!(test_32, NonMaxI32, NonMinI32, i32, NonMaxU32, NonMinU32, u32);
+    test_encoding!(test_64, NonMaxI64, NonMinI64, i64, NonMaxU64, NonMinU64, u64);
+    test_encoding!(test_128, NonMaxI128, NonMinI128, i128, NonMaxU128, NonMinU128, u128);
+    test_encoding!(
+        test_size,
+        NonMaxIsize,
+        NonMinIsize,
+        isize,
+        NonMaxUsize,
+        NonMinUsize,
+        usize
+    );
+
+    #[test]
+    fn test_exhaustive_i8() {
+        for x in i8::MIN + 1..=i8::MAX {
+            let x = NonMinI8::new(x).unwrap();
+            let y = NonMinI8::new(x.get().max(i8::MIN + 2) - 1).unwrap();
+            assert_eq!(NonMinI8::zigzag_decode(x.zigzag_encode()), x);
+            assert_eq!(x.cmp(&y), x.to_offset_binary().cmp(&y.to_offset_binary()));
+        }
+    }
+}
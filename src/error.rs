@@ -0,0 +1,179 @@
+//! Error types.
+
+use core::fmt;
+
+/// The error type returned when a checked conversion into a `NonMinX`/`NonMaxX` type fails.
+///
+/// ```
+/// # use nonminmax::*;
+/// use core::convert::TryFrom;
+///
+/// let err = NonMaxU8::try_from(255u8).unwrap_err();
+/// assert_eq!(err.kind(), &NonMinMaxErrorKind::Max);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NonMinMaxError {
+    kind: NonMinMaxErrorKind,
+}
+
+/// Enum to store the various kinds of errors that can cause a conversion to fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NonMinMaxErrorKind {
+    /// The value equals the minimum value of its type, which is excluded.
+    Min,
+    /// The value equals the maximum value of its type, which is excluded.
+    Max,
+    /// The value is too large to be stored in the target type.
+    PosOverflow,
+    /// The value is too small to be stored in the target type.
+    NegOverflow,
+    /// The value equals the value excluded by a `NonValueX` type.
+    Value,
+    /// The value is one of the values reserved by a `NonMaxKX` type. Since these types require the
+    /// `nightly` feature, this kind is only produced with that feature enabled.
+    Reserved,
+}
+
+impl NonMinMaxError {
+    pub(crate) fn new(kind: NonMinMaxErrorKind) -> Self {
+        Self { kind }
+    }
+
+    /// Creates the error for a `value` which is out of range of the target type.
+    pub(crate) fn overflow<T: Default + PartialOrd>(value: T) -> Self {
+        if value < T::default() {
+            Self::new(NonMinMaxErrorKind::NegOverflow)
+        } else {
+            Self::new(NonMinMaxErrorKind::PosOverflow)
+        }
+    }
+
+    /// Outputs the detailed cause of the conversion failure.
+    pub fn kind(&self) -> &NonMinMaxErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for NonMinMaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self.kind {
+            NonMinMaxErrorKind::Min => "value equals the excluded minimum value",
+            NonMinMaxErrorKind::Max => "value equals the excluded maximum value",
+            NonMinMaxErrorKind::PosOverflow => "value too large to fit in target type",
+            NonMinMaxErrorKind::NegOverflow => "value too small to fit in target type",
+            NonMinMaxErrorKind::Value => "value equals the excluded value",
+            NonMinMaxErrorKind::Reserved => "value equals a reserved value",
+        })
+    }
+}
+
+impl core::error::Error for NonMinMaxError {}
+
+/// The error type returned when parsing a `NonMinX`/`NonMaxX` type from a string fails.
+///
+/// ```
+/// # use nonminmax::*;
+/// let err = "255".parse::<NonMaxU8>().unwrap_err();
+/// assert_eq!(err.kind(), &ParseErrorKind::Max);
+///
+/// let err = "256".parse::<NonMaxU8>().unwrap_err();
+/// assert_eq!(err.kind(), &ParseErrorKind::PosOverflow);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+}
+
+/// Enum to store the various kinds of errors that can cause parsing to fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The string is empty.
+    Empty,
+    /// The string contains an invalid digit, or only consists of a sign or prefix.
+    InvalidDigit,
+    /// The value is too large to be stored in the target type.
+    PosOverflow,
+    /// The value is too small to be stored in the target type.
+    NegOverflow,
+    /// The value equals the minimum value of its type, which is excluded.
+    Min,
+    /// The value equals the maximum value of its type, which is excluded.
+    Max,
+    /// The value equals the value excluded by a `NonValueX` type.
+    Value,
+    /// The value is one of the values reserved by a `NonMaxKX` type. Since these types require the
+    /// `nightly` feature, this kind is only produced with that feature enabled.
+    Reserved,
+}
+
+impl ParseError {
+    pub(crate) fn new(kind: ParseErrorKind) -> Self {
+        Self { kind }
+    }
+
+    /// Outputs the detailed cause of the parsing failure.
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+impl From<NonMinMaxError> for ParseError {
+    fn from(err: NonMinMaxError) -> Self {
+        Self::new(match err.kind {
+            NonMinMaxErrorKind::Min => ParseErrorKind::Min,
+            NonMinMaxErrorKind::Max => ParseErrorKind::Max,
+            NonMinMaxErrorKind::PosOverflow => ParseErrorKind::PosOverflow,
+            NonMinMaxErrorKind::NegOverflow => ParseErrorKind::NegOverflow,
+            NonMinMaxErrorKind::Value => ParseErrorKind::Value,
+            NonMinMaxErrorKind::Reserved => ParseErrorKind::Reserved,
+        })
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self.kind {
+            ParseErrorKind::Empty => "cannot parse integer from empty string",
+            ParseErrorKind::InvalidDigit => "invalid digit found in string",
+            ParseErrorKind::PosOverflow => "number too large to fit in target type",
+            ParseErrorKind::NegOverflow => "number too small to fit in target type",
+            ParseErrorKind::Min => "number equals the excluded minimum value",
+            ParseErrorKind::Max => "number equals the excluded maximum value",
+            ParseErrorKind::Value => "number equals the excluded value",
+            ParseErrorKind::Reserved => "number equals a reserved value",
+        })
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn test_display() {
+        let err = NonMinMaxError::new(NonMinMaxErrorKind::Min);
+        assert_eq!(err.to_string(), "value equals the excluded minimum value");
+
+        let err = NonMinMaxError::new(NonMinMaxErrorKind::Max);
+        assert_eq!(err.to_string(), "value equals the excluded maximum value");
+
+        let err = NonMinMaxError::overflow(300i32);
+        assert_eq!(err.to_string(), "value too large to fit in target type");
+
+        let err = NonMinMaxError::overflow(-300i32);
+        assert_eq!(err.to_string(), "value too small to fit in target type");
+
+        let err = ParseError::from(NonMinMaxError::new(NonMinMaxErrorKind::Max));
+        assert_eq!(err.to_string(), "number equals the excluded maximum value");
+
+        let err = ParseError::new(ParseErrorKind::InvalidDigit);
+        assert_eq!(err.to_string(), "invalid digit found in string");
+    }
+}
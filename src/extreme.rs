@@ -0,0 +1,370 @@
+//! Signed integer types which can be neither their minimum nor their maximum value.
+//!
+//! Excluding both extremes keeps the range away from the boundaries of the primitive. Note that the
+//! range `MIN + 1..=MAX - 1` is not symmetric, since `-(MIN + 1)` equals `MAX`. Hence, negation and
+//! `abs` return a `NonMinIX` (whose range is symmetric), which never fails, while `checked_neg` and
+//! `checked_abs` stay within the type. `signum`, `unsigned_abs` and `abs_diff` can never fail
+//! either.
+//!
+//! On stable, the value is stored as `value ^ MIN` in a `NonZeroIX`, which provides a single niche
+//! like `NonMinIX`. With the `nightly` feature, the value is stored in a pattern type which
+//! declares the valid range, which provides a niche for both excluded values. For example,
+//! `Option<Option<NonExtremeI32>>` then takes up the same amount of space as an `i32`.
+
+use crate::error::{NonMinMaxError, NonMinMaxErrorKind};
+use crate::{NonMax, NonMin};
+use core::convert::TryFrom;
+use core::fmt;
+#[cfg(not(feature = "nightly"))]
+use core::num::{NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize};
+
+/// The pattern type which stores the value in the `nightly` backend, and the bounds of its range.
+#[cfg(feature = "nightly")]
+trait Extremes {
+    type Repr: Copy;
+    const LOWEST: Self;
+    const HIGHEST: Self;
+}
+
+macro_rules! impl_nonextreme {
+    ($struct:ident, $nonzero:ident, $prim:ident, $unsigned:ident) => {
+        #[cfg(feature = "nightly")]
+        impl Extremes for $prim {
+            type Repr = core::pattern_type!(
+                $prim is <$prim as Extremes>::LOWEST..=<$prim as Extremes>::HIGHEST
+            );
+            const LOWEST: Self = <$prim>::MIN + 1;
+            const HIGHEST: Self = <$prim>::MAX - 1;
+        }
+
+        #[cfg(not(feature = "nightly"))]
+        doc_comment! {
+            concat!("An integer of type `", stringify!($prim), "` which is known to equal neither `",
+            stringify!($prim), "::MIN` nor `", stringify!($prim), "::MAX`."),
+            #[derive(Clone, Copy)]
+            #[repr(transparent)]
+            pub struct $struct {
+                value: $nonzero,
+            }
+        }
+
+        #[cfg(feature = "nightly")]
+        doc_comment! {
+            concat!("An integer of type `", stringify!($prim), "` which is known to equal neither `",
+            stringify!($prim), "::MIN` nor `", stringify!($prim), "::MAX`."),
+            #[derive(Clone, Copy)]
+            #[repr(transparent)]
+            pub struct $struct {
+                value: <$prim as Extremes>::Repr,
+            }
+        }
+
+        impl $struct {
+            doc_comment! {
+                concat!("The smallest value that can be represented by `", stringify!($struct), "`."),
+                pub const MIN: Self = unsafe { Self::new_unchecked(<$prim>::MIN + 1) };
+            }
+
+            doc_comment! {
+                concat!("The largest value that can be represented by `", stringify!($struct), "`."),
+                pub const MAX: Self = unsafe { Self::new_unchecked(<$prim>::MAX - 1) };
+            }
+
+            /// The size of this integer type in bits.
+            pub const BITS: u32 = <$prim>::BITS;
+
+            /// The value zero.
+            pub const ZERO: Self = unsafe { Self::new_unchecked(0) };
+
+            /// The value one.
+            pub const ONE: Self = unsafe { Self::new_unchecked(1) };
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($struct), "` by checking if the ",
+                "value is neither `", stringify!($prim), "::MIN` nor `", stringify!($prim), "::MAX`."),
+                #[inline(always)]
+                pub const fn new(value: $prim) -> Option<Self> {
+                    if value != <$prim>::MIN && value != <$prim>::MAX {
+                        unsafe { Some(Self::new_unchecked(value)) }
+                    } else {
+                        None
+                    }
+                }
+            }
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($struct), "`, or panics if the value ",
+                "is `", stringify!($prim), "::MIN` or `", stringify!($prim), "::MAX`.\n",
+                " # Panics\n",
+                "Panics if the value is equal to `", stringify!($prim), "::MIN` or `",
+                stringify!($prim), "::MAX`. In a `const` context, this results in a compile-time ",
+                "error."),
+                #[inline]
+                #[track_caller]
+                pub const fn new_or_panic(value: $prim) -> Self {
+                    match Self::new(value) {
+                        Some(value) => value,
+                        None => panic!(concat!("value equals `", stringify!($prim), "::MIN` or `",
+                            stringify!($prim), "::MAX`")),
+                    }
+                }
+            }
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($struct), "` without checking if ",
+                "the value is neither `", stringify!($prim), "::MIN` nor `", stringify!($prim), "::MAX`.\n",
+                " # Safety\n",
+                "The value cannot be equal to `", stringify!($prim), "::MIN` or `",
+                stringify!($prim), "::MAX`."),
+                #[inline(always)]
+                pub const unsafe fn new_unchecked(value: $prim) -> Self {
+                    #[cfg(not(feature = "nightly"))]
+                    let value = $nonzero::new_unchecked(value ^ <$prim>::MIN);
+                    #[cfg(feature = "nightly")]
+                    let value = core::mem::transmute::<$prim, <$prim as Extremes>::Repr>(value);
+
+                    Self { value }
+                }
+            }
+
+            /// Returns the integer value.
+            #[inline(always)]
+            pub const fn get(self) -> $prim {
+                #[cfg(not(feature = "nightly"))]
+                return self.value.get() ^ <$prim>::MIN;
+                #[cfg(feature = "nightly")]
+                return unsafe { core::mem::transmute::<<$prim as Extremes>::Repr, $prim>(self.value) };
+            }
+
+            /// Returns a number representing the sign of `self`: `-1`, `0` or `1`. Since these
+            /// values are never excluded, this cannot fail.
+            #[inline]
+            pub const fn signum(self) -> Self {
+                unsafe { Self::new_unchecked(self.get().signum()) }
+            }
+
+            doc_comment! {
+                concat!("Computes the absolute value of `self` as a `NonMin<", stringify!($prim), ">`. ",
+                "This can never fail, but the result may be `", stringify!($prim), "::MAX`."),
+                #[inline]
+                pub fn abs(self) -> NonMin<$prim> {
+                    unsafe { NonMin::<$prim>::new_unchecked(self.get().abs()) }
+                }
+            }
+
+            doc_comment! {
+                concat!("Checked absolute value. Computes `self.abs()`, returning `None` if the ",
+                "result is `", stringify!($prim), "::MAX`."),
+                #[inline]
+                pub fn checked_abs(self) -> Option<Self> {
+                    Self::new(self.get().abs())
+                }
+            }
+
+            doc_comment! {
+                concat!("Computes the absolute value of `self` as a `NonMax<", stringify!($unsigned),
+                ">`. This can never fail."),
+                #[inline]
+                pub fn unsigned_abs(self) -> NonMax<$unsigned> {
+                    unsafe { NonMax::<$unsigned>::new_unchecked(self.get().unsigned_abs()) }
+                }
+            }
+
+            doc_comment! {
+                concat!("Computes the absolute difference between `self` and `other` as a `NonMax<",
+                stringify!($unsigned), ">`. This can never fail."),
+                #[inline]
+                pub fn abs_diff(self, other: Self) -> NonMax<$unsigned> {
+                    unsafe { NonMax::<$unsigned>::new_unchecked(self.get().abs_diff(other.get())) }
+                }
+            }
+        }
+
+        impl core::ops::Neg for $struct {
+            type Output = NonMin<$prim>;
+
+            /// Negates `self`. The result is a `NonMinIX` since `-(MIN + 1)` equals `MAX`.
+            #[inline]
+            fn neg(self) -> NonMin<$prim> {
+                unsafe { NonMin::<$prim>::new_unchecked(-self.get()) }
+            }
+        }
+
+        impl From<$struct> for $prim {
+            fn from(nontype: $struct) -> Self {
+                nontype.get()
+            }
+        }
+
+        impl TryFrom<$prim> for $struct {
+            type Error = NonMinMaxError;
+
+            fn try_from(value: $prim) -> Result<Self, Self::Error> {
+                Self::new(value).ok_or_else(|| {
+                    NonMinMaxError::new(if value == <$prim>::MIN {
+                        NonMinMaxErrorKind::Min
+                    } else {
+                        NonMinMaxErrorKind::Max
+                    })
+                })
+            }
+        }
+
+        impl From<$struct> for NonMax<$prim> {
+            #[inline]
+            fn from(value: $struct) -> Self {
+                unsafe { Self::new_unchecked(value.get()) }
+            }
+        }
+
+        impl From<$struct> for NonMin<$prim> {
+            #[inline]
+            fn from(value: $struct) -> Self {
+                unsafe { Self::new_unchecked(value.get()) }
+            }
+        }
+
+        impl TryFrom<NonMax<$prim>> for $struct {
+            type Error = NonMinMaxError;
+
+            #[inline]
+            fn try_from(value: NonMax<$prim>) -> Result<Self, Self::Error> {
+                Self::try_from(value.get())
+            }
+        }
+
+        impl TryFrom<NonMin<$prim>> for $struct {
+            type Error = NonMinMaxError;
+
+            #[inline]
+            fn try_from(value: NonMin<$prim>) -> Result<Self, Self::Error> {
+                Self::try_from(value.get())
+            }
+        }
+
+        // Pattern types only implement `Clone` and `Copy`, so these are not derived.
+        impl PartialEq for $struct {
+            fn eq(&self, other: &Self) -> bool {
+                self.get() == other.get()
+            }
+        }
+
+        impl Eq for $struct {}
+
+        impl core::hash::Hash for $struct {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.get().hash(state)
+            }
+        }
+
+        impl PartialOrd for $struct {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $struct {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.get().cmp(&other.get())
+            }
+        }
+
+        impl fmt::Debug for $struct {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($struct)).field(&self.get()).finish()
+            }
+        }
+
+        impl_fmt!($struct, Display, Binary, Octal, LowerHex, UpperHex, LowerExp, UpperExp);
+        impl_checked_ops!($struct, $prim);
+    };
+}
+
+impl_nonextreme!(NonExtremeI8, NonZeroI8, i8, u8);
+impl_nonextreme!(NonExtremeI16, NonZeroI16, i16, u16);
+impl_nonextreme!(NonExtremeI32, NonZeroI32, i32, u32);
+impl_nonextreme!(NonExtremeI64, NonZeroI64, i64, u64);
+impl_nonextreme!(NonExtremeI128, NonZeroI128, i128, u128);
+impl_nonextreme!(NonExtremeIsize, NonZeroIsize, isize, usize);
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use core::convert::TryFrom;
+
+    macro_rules! test_nonextreme {
+        ($test_name:ident, $struct:ident, $nonmax:ident, $nonmin:ident, $prim:ident, $unsigned:ident) => {
+            #[test]
+            fn $test_name() {
+                for &value in &[0, 1, -1, 42, -42, <$prim>::MIN + 1, <$prim>::MAX - 1] {
+                    let x = $struct::new(value).unwrap();
+                    assert_eq!(x.get(), value);
+                    assert_eq!((-x).get(), -value);
+                    assert_eq!(x.abs().get(), value.abs());
+                    assert_eq!(x.unsigned_abs().get(), value.unsigned_abs());
+                    assert_eq!(x.signum().get(), value.signum());
+                    assert_eq!(<$prim>::from(x), value);
+                    assert_eq!($struct::try_from(value), Ok(x));
+
+                    assert_eq!($nonmax::from(x).get(), value);
+                    assert_eq!($nonmin::from(x).get(), value);
+                    assert_eq!($struct::try_from($nonmax::from(x)), Ok(x));
+                    assert_eq!($struct::try_from($nonmin::from(x)), Ok(x));
+                }
+
+                assert_eq!($struct::new(<$prim>::MIN), None);
+                assert_eq!($struct::new(<$prim>::MAX), None);
+                assert_eq!((-$struct::MIN).get(), <$prim>::MAX);
+                assert_eq!((-$struct::MAX).get(), <$prim>::MIN + 2);
+                assert_eq!($struct::MIN.abs().get(), <$prim>::MAX);
+                assert_eq!($struct::MIN.checked_abs(), None);
+                assert_eq!($struct::MIN.checked_neg(), None);
+                assert_eq!($struct::MAX.checked_neg().unwrap().get(), <$prim>::MIN + 2);
+                assert_eq!(
+                    $struct::MIN.abs_diff($struct::MAX).get(),
+                    (<$prim>::MAX as $unsigned) * 2 - 1
+                );
+                assert_eq!($struct::ZERO.signum(), $struct::ZERO);
+                assert_eq!($struct::ONE.get(), 1);
+                assert!($struct::MIN < $struct::MAX);
+
+                let min = $nonmax::new(<$prim>::MIN).unwrap();
+                let err = $struct::try_from(min).unwrap_err();
+                assert_eq!(err.kind(), &NonMinMaxErrorKind::Min);
+                let max = $nonmin::new(<$prim>::MAX).unwrap();
+                let err = $struct::try_from(max).unwrap_err();
+                assert_eq!(err.kind(), &NonMinMaxErrorKind::Max);
+
+                assert_eq!($struct::MAX.checked_add($struct::ONE), None);
+                assert_eq!(($struct::ONE + 1).get(), 2);
+
+                use core::mem::size_of;
+                assert_eq!(size_of::<$struct>(), size_of::<$prim>());
+                assert_eq!(size_of::<Option<$struct>>(), size_of::<$prim>());
+                #[cfg(feature = "nightly")]
+                assert_eq!(size_of::<Option<Option<$struct>>>(), size_of::<$prim>());
+            }
+        };
+    }
+
+    test_nonextreme!(test_i8, NonExtremeI8, NonMaxI8, NonMinI8, i8, u8);
+    test_nonextreme!(test_i16, NonExtremeI16, NonMaxI16, NonMinI16, i16, u16);
+    test_nonextreme!(test_i32, NonExtremeI32, NonMaxI32, NonMinI32, i32, u32);
+    test_nonextreme!(test_i64, NonExtremeI64, NonMaxI64, NonMinI64, i64, u64);
+    test_nonextreme!(
+        test_i128,
+        NonExtremeI128,
+        NonMaxI128,
+        NonMinI128,
+        i128,
+        u128
+    );
+    test_nonextreme!(
+        test_isize,
+        NonExtremeIsize,
+        NonMaxIsize,
+        NonMinIsize,
+        isize,
+        usize
+    );
+}
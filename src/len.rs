@@ -0,0 +1,85 @@
+//! Conversions between indices (`NonMaxUX`) and lengths (`NonZeroUX`).
+//!
+//! Any index into a collection is one less than some non-zero length and vice versa, so a
+//! `NonMaxUX` plus one always fits in a `NonZeroUX`, and a `NonZeroUX` minus one always fits in a
+//! `NonMaxUX`.
+
+use crate::{NonMaxU128, NonMaxU16, NonMaxU32, NonMaxU64, NonMaxU8, NonMaxUsize};
+use core::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize};
+
+macro_rules! impl_len {
+    ($struct:ident, $nonzero:ident) => {
+        impl $struct {
+            doc_comment! {
+                concat!("Returns `self + 1` as a `", stringify!($nonzero), "`, i.e., the length ",
+                "of the shortest collection for which `self` is a valid index. This can never overflow."),
+                #[inline]
+                pub fn to_len(self) -> $nonzero {
+                    unsafe { $nonzero::new_unchecked(self.get() + 1) }
+                }
+            }
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($struct), "` from `len - 1`, i.e., the ",
+                "index of the last element of a collection of length `len`. This can never underflow."),
+                #[inline]
+                pub fn from_len(len: $nonzero) -> Self {
+                    unsafe { Self::new_unchecked(len.get() - 1) }
+                }
+            }
+        }
+    };
+}
+
+impl_len!(NonMaxU8, NonZeroU8);
+impl_len!(NonMaxU16, NonZeroU16);
+impl_len!(NonMaxU32, NonZeroU32);
+impl_len!(NonMaxU64, NonZeroU64);
+impl_len!(NonMaxU128, NonZeroU128);
+impl_len!(NonMaxUsize, NonZeroUsize);
+
+impl NonMaxUsize {
+    /// Returns the index of the last element of `slice`, or `None` if the slice is empty.
+    ///
+    /// ```
+    /// # use nonminmax::*;
+    /// let last = NonMaxUsize::from_slice_len(&[1, 2, 3]).unwrap();
+    /// assert_eq!(last.get(), 2);
+    /// assert_eq!(last.to_len().get(), 3);
+    ///
+    /// assert_eq!(NonMaxUsize::from_slice_len::<u8>(&[]), None);
+    /// ```
+    #[inline]
+    pub fn from_slice_len<T>(slice: &[T]) -> Option<Self> {
+        NonZeroUsize::new(slice.len()).map(Self::from_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use core::num::*;
+
+    macro_rules! test_len {
+        ($test_name:ident, $struct:ident, $nonzero:ident, $prim:ident) => {
+            #[test]
+            fn $test_name() {
+                let x = $struct::new(41).unwrap();
+                assert_eq!(x.to_len().get(), 42);
+                assert_eq!($struct::from_len(x.to_len()), x);
+
+                let hi = $struct::new(<$prim>::MAX - 1).unwrap();
+                assert_eq!(hi.to_len().get(), <$prim>::MAX);
+                assert_eq!($struct::from_len($nonzero::new(<$prim>::MAX).unwrap()), hi);
+                assert_eq!($struct::from_len($nonzero::new(1).unwrap()).get(), 0);
+            }
+        };
+    }
+
+    test_len!(test_nonmaxu8, NonMaxU8, NonZeroU8, u8);
+    test_len!(test_nonmaxu16, NonMaxU16, NonZeroU16, u16);
+    test_len!(test_nonmaxu32, NonMaxU32, NonZeroU32, u32);
+    test_len!(test_nonmaxu64, NonMaxU64, NonZeroU64, u64);
+    test_len!(test_nonmaxu128, NonMaxU128, NonZeroU128, u128);
+    test_len!(test_nonmaxusize, NonMaxUsize, NonZeroUsize, usize);
+}
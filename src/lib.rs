@@ -1,4 +1,6 @@
 #![no_std]
+#![cfg_attr(feature = "nightly", feature(pattern_types, pattern_type_macro))]
+#![cfg_attr(feature = "nightly", allow(internal_features, incomplete_features))]
 //! # Integers types which cannot be their minimum/maximum value.
 //!
 //! The standard library contains a collection of `std::num::NonZeroX` types: integer types which
@@ -51,11 +53,136 @@
 //! assert!(size_of::<[Option<NonMaxU32>; 1000]>() == 4000);
 //! ```
 //!
+//! # Arithmetic
+//! All types support checked arithmetic (`checked_add`, `checked_sub`, ...) and the usual
+//! arithmetic operators, which panic if the result overflows or equals the excluded value. The
+//! `saturating_*` methods and the `Saturating` wrapper instead clamp the result to the nearest
+//! valid value, while the `wrapping_*` methods and the `Wrapping` wrapper wrap around modulo the
+//! number of valid values (skipping the excluded value). For `NonMaxUX`, the latter is arithmetic
+//! modulo `2^N - 1`, which is also available through the `ModMersenne` wrapper.
+//!
+//! ```
+//! # use nonminmax::*;
+//! let x = NonMaxU8::new(200).unwrap();
+//! assert_eq!((x + 50).get(), 250);
+//! assert_eq!(x.checked_add(x), None);
+//! assert_eq!(x.saturating_add(x).get(), 254);
+//! assert_eq!(x.wrapping_add(x).get(), 145);
+//! ```
+//!
+//! # Constants
+//! All constructors and `get` are `const fn`, and every type provides the associated constants
+//! `MIN`, `MAX`, `BITS` and `ONE`, as well as `ZERO` if zero is a valid value. This allows the
+//! types to be used in `const` and `static` tables. The `nonmax!` and `nonmin!` macros create a
+//! value from a constant and reject the excluded value at compile time.
+//!
+//! ```
+//! # use nonminmax::*;
+//! static TABLE: [Option<NonMaxU32>; 3] = [NonMaxU32::new(1), None, Some(NonMaxU32::MAX)];
+//!
+//! const FIRST: u32 = match TABLE[0] {
+//!     Some(x) => x.get(),
+//!     None => 0,
+//! };
+//! assert_eq!(FIRST, 1);
+//! assert_eq!(NonMaxU32::MAX.get(), u32::MAX - 1);
+//! assert_eq!(NonMinI8::MIN.get(), -127);
+//!
+//! const IDS: [NonMaxU32; 2] = [nonmax!(1u32), nonmax!(42u32)];
+//! assert_eq!(IDS[1].get(), 42);
+//! ```
+//!
+//! # Generic types
+//! The `NonMaxX`/`NonMinX` types are aliases of the generic `NonMax<T>`/`NonMin<T>` types, which
+//! are implemented for every primitive integer `T` through the sealed `NicheInt` trait. This is
+//! similar to `core::num::NonZero<T>`, and allows for code which is generic over the width.
+//!
+//! ```
+//! # use nonminmax::*;
+//! fn first<T: NicheInt>(values: &[T]) -> Option<NonMax<T>> {
+//!     values.first().copied().and_then(NonMax::new)
+//! }
+//!
+//! let x: Option<NonMaxU32> = first(&[1u32, 2, 3]);
+//! assert_eq!(x, NonMaxU32::new(1));
+//! assert_eq!(first(&[u8::MAX]), None);
+//! ```
+//!
+//! # Arbitrary excluded values
+//! The `NonValueX<V>` types exclude an arbitrary value `V` instead, such as a sentinel used in a
+//! wire format. `NonValueX<{ X::MAX }>` and `NonValueX<{ X::MIN }>` convert for free into
+//! `NonMaxX` and `NonMinX`, respectively.
+//!
+//! ```
+//! # use nonminmax::*;
+//! type Id = NonValueU32<0xDEAD>;
+//!
+//! assert_eq!(Id::new(0xBEEF).unwrap().get(), 0xBEEF);
+//! assert_eq!(Id::new(0xDEAD), None);
+//! assert_eq!(std::mem::size_of::<Option<Id>>(), 4);
+//! ```
+//!
+//! # Excluding both extremes
+//! The `NonExtremeIX` types exclude both `MIN` and `MAX` of a signed integer. With the `nightly`
+//! feature, these types provide a niche for both excluded values, which requires a nightly
+//! compiler.
+//!
+//! ```
+//! # use nonminmax::*;
+//! let x = NonExtremeI32::new(-5).unwrap();
+//! assert_eq!(x.signum().get(), -1);
+//! assert_eq!((-x).get(), 5);
+//! assert_eq!(NonExtremeI32::new(i32::MAX), None);
+//! ```
+//!
+//! # Multiple niches
+//! With the `nightly` feature, the `NonMaxKX` types (for `K` of 2, 3 or 4) exclude the `K` largest
+//! values, each of which is a separate niche. Then `Option<Option<NonMax2X>>`, or an enum with a
+//! tombstone variant, takes up the same amount of space as `X`. `reserved_slot` tells which of the
+//! reserved values an encoded word holds.
+//!
+//! ```
+//! # #[cfg(feature = "nightly")] {
+//! # use nonminmax::*;
+//! assert_eq!(std::mem::size_of::<Option<Option<NonMax2U32>>>(), 4);
+//! assert_eq!(NonMax2U32::reserved_slot(u32::MAX), Some(1));
+//! assert_eq!(NonMax2U32::new(u32::MAX - 1), None);
+//! # }
+//! ```
+//!
+//! # Ranged integers
+//! The `RangedX<LO, HI>` types only allow values in the range `LO..=HI`. As long as the range does
+//! not include every value, `Option<RangedX<LO, HI>>` takes up the same amount of space as `X`.
+//! Arithmetic either returns an `Option`, or a type with a wider range which is checked at compile
+//! time to contain every possible result.
+//!
+//! ```
+//! # use nonminmax::*;
+//! let x = RangedU32::<1, 6>::new(4).unwrap();
+//! let y = RangedU32::<1, 6>::new(5).unwrap();
+//! let sum: RangedU32<2, 12> = x.widening_add(y);
+//! assert_eq!(sum.get(), 9);
+//! assert_eq!(x.checked_add(y), None);
+//! assert_eq!(std::mem::size_of::<Option<RangedU32<1, 6>>>(), 4);
+//! ```
+//!
+//! A range which includes every value leaves no niche, and is rejected at compile time.
+//!
+//! ```compile_fail
+//! # use nonminmax::*;
+//! let x = RangedU8::<0, 255>::new(42);
+//! ```
+//!
 //! # Internal details
 //! Internally, these types work by wrapping the existing `NonZeroX` types and xor-ing with a mask when
 //! accessing the inner value. This means that there is the cost of a single `xor` instruction each
 //! time `get` is called.
 //!
+//! With the `nightly` feature, which requires a nightly compiler, the `NonMaxX`/`NonMinX` types
+//! instead store the value unchanged in a pattern type which declares the valid range. Then `get`
+//! and `new_unchecked` are free, and the bytes in memory equal the value. The public API and the
+//! niche filling optimization are the same for both backends.
+//!
 //! # Supported types
 //! The following types are supported
 //! - `i8`/`u8`
@@ -68,10 +195,6 @@
 //!
 
 use core::fmt;
-use core::num::{
-    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
-    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
-};
 
 macro_rules! doc_comment {
     ($x:expr, $($tt:tt)*) => {
@@ -80,11 +203,232 @@ macro_rules! doc_comment {
     }
 }
 
-/// Testing testing
+macro_rules! impl_fmt {
+    ($struct:ident, $($trait:ident),*) => {
+        impl_fmt!([] $struct, $($trait),*);
+    };
+    (@impl [$($gen:tt)*] $struct:ty, $trait:ident) => {
+        impl<$($gen)*> fmt::$trait for $struct {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::$trait::fmt(&self.get(), f)
+            }
+        }
+    };
+    ($gen:tt $struct:ty, $($trait:ident),*) => {
+        $(impl_fmt!(@impl $gen $struct, $trait);)*
+    };
+}
 
-macro_rules! impl_nontype {
-    ($struct:ident, $nonzero:ident, $prim:ident, $mask:expr) => {
+#[macro_use]
+mod ops;
+#[macro_use]
+mod bits;
+mod cmp;
+mod complement;
+mod convert;
+mod division;
+mod encoding;
+mod error;
+mod extreme;
+mod len;
+mod literal;
+mod mersenne;
+mod niche;
+mod nonvalue;
+mod nonzero;
+mod parse;
+mod ranged;
+#[cfg(feature = "nightly")]
+mod reserved;
+#[macro_use]
+mod saturating;
+mod symmetric;
+#[macro_use]
+mod wrapping;
+
+pub use error::{NonMinMaxError, NonMinMaxErrorKind, ParseError, ParseErrorKind};
+pub use extreme::{
+    NonExtremeI128, NonExtremeI16, NonExtremeI32, NonExtremeI64, NonExtremeI8, NonExtremeIsize,
+};
+pub use mersenne::ModMersenne;
+pub use niche::NicheInt;
+pub use nonvalue::{
+    NonValueI128, NonValueI16, NonValueI32, NonValueI64, NonValueI8, NonValueIsize,
+};
+pub use nonvalue::{
+    NonValueU128, NonValueU16, NonValueU32, NonValueU64, NonValueU8, NonValueUsize,
+};
+pub use ranged::{RangedI128, RangedI16, RangedI32, RangedI64, RangedI8, RangedIsize};
+pub use ranged::{RangedU128, RangedU16, RangedU32, RangedU64, RangedU8, RangedUsize};
+#[cfg(feature = "nightly")]
+pub use reserved::{NonMax2I128, NonMax2I16, NonMax2I32, NonMax2I64, NonMax2I8, NonMax2Isize};
+#[cfg(feature = "nightly")]
+pub use reserved::{NonMax2U128, NonMax2U16, NonMax2U32, NonMax2U64, NonMax2U8, NonMax2Usize};
+#[cfg(feature = "nightly")]
+pub use reserved::{NonMax3I128, NonMax3I16, NonMax3I32, NonMax3I64, NonMax3I8, NonMax3Isize};
+#[cfg(feature = "nightly")]
+pub use reserved::{NonMax3U128, NonMax3U16, NonMax3U32, NonMax3U64, NonMax3U8, NonMax3Usize};
+#[cfg(feature = "nightly")]
+pub use reserved::{NonMax4I128, NonMax4I16, NonMax4I32, NonMax4I64, NonMax4I8, NonMax4Isize};
+#[cfg(feature = "nightly")]
+pub use reserved::{NonMax4U128, NonMax4U16, NonMax4U32, NonMax4U64, NonMax4U8, NonMax4Usize};
+pub use saturating::Saturating;
+pub use wrapping::Wrapping;
+
+macro_rules! impl_generic {
+    ($generic:ident, $mask:ident, $neighbour:ident, $lowest:ident, $name:ident, $repr:ident, $doc:expr) => {
+        doc_comment! {
+            concat!("An integer of type `T` which is known to not equal `T::", stringify!($mask), "`.
+
+This is the generic counterpart of the `", stringify!($generic), "X` type aliases, similar to
+`core::num::NonZero<T>`. It allows for the niche filling optimization, meaning that
+`Option<", stringify!($generic), "<T>>` takes up the same amount of space as `T`.
+
+```
+# use nonminmax::*;
+fn store<T: NicheInt>(slot: &mut Option<", stringify!($generic), "<T>>, value: T) -> bool {
+    *slot = ", stringify!($generic), "::new(value);
+    slot.is_some()
+}
+
+let mut slot = None;
+assert!(store(&mut slot, ", $doc, "));
+assert_eq!(slot.map(", stringify!($generic), "::get), Some(", $doc, "));
+```"),
+            #[derive(Clone, Copy)]
+            #[cfg_attr(not(feature = "nightly"), derive(PartialEq, Eq, Hash))]
+            #[repr(transparent)]
+            pub struct $generic<T: NicheInt> {
+                #[cfg(not(feature = "nightly"))]
+                value: T::NonZero,
+                #[cfg(feature = "nightly")]
+                value: <T as niche::sealed::Sealed>::$repr,
+            }
+        }
+
+        impl<T: NicheInt> $generic<T> {
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($generic), "<T>` by checking if the value is not `T::", stringify!($mask), "`."),
+                #[inline(always)]
+                pub const fn new(value: T) -> Option<Self> {
+                    if !niche::eq(value, T::$mask) {
+                        unsafe { Some(Self::new_unchecked(value)) }
+                    } else {
+                        None
+                    }
+                }
+            }
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($generic), "<T>`, or panics if the value is `T::", stringify!($mask), "`.\n",
+                " # Panics\n",
+                "Panics if the value is equal to `T::", stringify!($mask), "`. In a `const` context, this ",
+                "results in a compile-time error."),
+                #[inline]
+                #[track_caller]
+                pub const fn new_or_panic(value: T) -> Self {
+                    match Self::new(value) {
+                        Some(value) => value,
+                        None => panic!(concat!("value equals `T::", stringify!($mask), "`")),
+                    }
+                }
+            }
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($generic), "<T>` without checking if the value is not `T::", stringify!($mask), "`.\n",
+                " # Safety\n",
+                "The value cannot be equal to `T::", stringify!($mask), "`."),
+                #[inline(always)]
+                pub const unsafe fn new_unchecked(value: T) -> Self {
+                    #[cfg(not(feature = "nightly"))]
+                    let value = niche::encode(value, T::$mask);
+                    #[cfg(feature = "nightly")]
+                    let value = niche::store(value);
+
+                    Self { value }
+                }
+            }
+
+            /// Returns the integer value.
+            #[inline(always)]
+            pub const fn get(self) -> T {
+                #[cfg(not(feature = "nightly"))]
+                return niche::decode(self.value, T::$mask);
+                #[cfg(feature = "nightly")]
+                return niche::load(self.value);
+            }
+        }
 
+        // Pattern types do not implement these traits, so the `nightly` backend compares the
+        // values instead.
+        #[cfg(feature = "nightly")]
+        impl<T: NicheInt> PartialEq for $generic<T> {
+            fn eq(&self, other: &Self) -> bool {
+                self.get() == other.get()
+            }
+        }
+
+        #[cfg(feature = "nightly")]
+        impl<T: NicheInt> Eq for $generic<T> {}
+
+        #[cfg(feature = "nightly")]
+        impl<T: NicheInt> core::hash::Hash for $generic<T> {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.get().hash(state);
+            }
+        }
+
+        impl<T: NicheInt> PartialOrd for $generic<T> {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<T: NicheInt> Ord for $generic<T> {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.get().cmp(&other.get())
+            }
+        }
+
+        impl<T: NicheInt> fmt::Debug for $generic<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                // Use `debug_tuple` so the formatter flags, such as `{:#x?}`, apply to the value.
+                f.debug_tuple(T::$name).field(&self.get()).finish()
+            }
+        }
+
+        impl_fmt!([T: NicheInt] $generic<T>, Display, Binary, Octal, LowerHex, UpperHex, LowerExp, UpperExp);
+
+        impl_checked_ops!([T: NicheInt] $generic<T>, T);
+        impl_checked_bit_ops!([T: NicheInt] $generic<T>, T);
+        impl_bit_counts!([T: NicheInt] $generic<T>, T);
+        impl_saturating_ops!([T: NicheInt] $generic<T>, T, T::$mask, T::$neighbour);
+        impl_wrapping_ops!([T: NicheInt] $generic<T>, T, T::$mask, T::$lowest);
+    };
+}
+
+impl_generic!(
+    NonMax,
+    MAX,
+    BELOW_MAX,
+    MIN,
+    NON_MAX_NAME,
+    NonMaxRepr,
+    "42u16"
+);
+impl_generic!(
+    NonMin,
+    MIN,
+    ABOVE_MIN,
+    ABOVE_MIN,
+    NON_MIN_NAME,
+    NonMinRepr,
+    "-7i64"
+);
+
+macro_rules! impl_nontype {
+    ($struct:ident, $generic:ident, $prim:ident, $mask:expr) => {
         doc_comment! {
             concat!("
             An integer of type `", stringify!($prim),"` which is known to not equal `", stringify!($mask), "`.
@@ -110,43 +454,37 @@ macro_rules! impl_nontype {
             assert_eq!(size_of::<", stringify!($prim) ,">(), size_of::<Option<", stringify!($struct) ,">>());
             ```",
             ),
-            #[derive(Clone, Copy, PartialEq, Eq, Hash)]
-            #[repr(transparent)]
-            pub struct $struct {
-                value: $nonzero,
-            }
+            pub type $struct = $generic<$prim>;
         }
 
         impl $struct {
             doc_comment! {
-                concat!("Creates an instance of `", stringify!($struct), "` by checking if the value is not `", stringify!($mask), "`."),
-                #[inline(always)]
-                pub fn new(value: $prim) -> Option<Self> {
-                    if value != $mask {
-                        unsafe { Some(Self::new_unchecked(value)) }
+                concat!("The smallest value that can be represented by `", stringify!($struct), "`."),
+                pub const MIN: Self = unsafe {
+                    Self::new_unchecked(if $mask == <$prim>::MIN {
+                        $mask.wrapping_add(1)
                     } else {
-                        None
-                    }
-                }
+                        <$prim>::MIN
+                    })
+                };
             }
 
             doc_comment! {
-                concat!("Creates an instance of `", stringify!($struct), "` without checking if the value is not `", stringify!($mask), "`.\n",
-                " # Safety\n",
-                "The value cannot be equal to `", stringify!($mask), "`."),
-                #[inline(always)]
-                pub unsafe fn new_unchecked(value: $prim) -> Self {
-                    let value = $nonzero::new_unchecked(value ^ $mask);
-
-                    Self { value }
-                }
+                concat!("The largest value that can be represented by `", stringify!($struct), "`."),
+                pub const MAX: Self = unsafe {
+                    Self::new_unchecked(if $mask == <$prim>::MAX {
+                        $mask.wrapping_sub(1)
+                    } else {
+                        <$prim>::MAX
+                    })
+                };
             }
 
-            /// Returns the integer value.
-            #[inline(always)]
-            pub fn get(self) -> $prim {
-                self.value.get() ^ $mask
-            }
+            /// The size of this integer type in bits.
+            pub const BITS: u32 = <$prim>::BITS;
+
+            /// The value one, which is never excluded.
+            pub const ONE: Self = unsafe { Self::new_unchecked(1) };
         }
 
         impl From<$struct> for $prim {
@@ -155,63 +493,125 @@ macro_rules! impl_nontype {
             }
         }
 
-        impl PartialOrd for $struct {
-            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
-                self.get().partial_cmp(&other.get())
-            }
-        }
+        impl core::convert::TryFrom<$prim> for $struct {
+            type Error = NonMinMaxError;
 
-        impl Ord for $struct {
-            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
-                self.get().cmp(&other.get())
+            fn try_from(value: $prim) -> Result<Self, Self::Error> {
+                Self::new(value).ok_or_else(|| {
+                    NonMinMaxError::new(if $mask == <$prim>::MAX {
+                        NonMinMaxErrorKind::Max
+                    } else {
+                        NonMinMaxErrorKind::Min
+                    })
+                })
             }
         }
 
-        impl fmt::Debug for $struct {
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                write!(f, concat!(stringify!($struct), "({:?})"), self.get())
+    }
+}
+
+impl_nontype!(NonMaxU8, NonMax, u8, u8::MAX);
+impl_nontype!(NonMaxU16, NonMax, u16, u16::MAX);
+impl_nontype!(NonMaxU32, NonMax, u32, u32::MAX);
+impl_nontype!(NonMaxU64, NonMax, u64, u64::MAX);
+impl_nontype!(NonMaxU128, NonMax, u128, u128::MAX);
+impl_nontype!(NonMaxUsize, NonMax, usize, usize::MAX);
+
+impl_nontype!(NonMaxI8, NonMax, i8, i8::MAX);
+impl_nontype!(NonMaxI16, NonMax, i16, i16::MAX);
+impl_nontype!(NonMaxI32, NonMax, i32, i32::MAX);
+impl_nontype!(NonMaxI64, NonMax, i64, i64::MAX);
+impl_nontype!(NonMaxI128, NonMax, i128, i128::MAX);
+impl_nontype!(NonMaxIsize, NonMax, isize, isize::MAX);
+
+impl_nontype!(NonMinU8, NonMin, u8, u8::MIN);
+impl_nontype!(NonMinU16, NonMin, u16, u16::MIN);
+impl_nontype!(NonMinU32, NonMin, u32, u32::MIN);
+impl_nontype!(NonMinU64, NonMin, u64, u64::MIN);
+impl_nontype!(NonMinU128, NonMin, u128, u128::MIN);
+impl_nontype!(NonMinUsize, NonMin, usize, usize::MIN);
+
+impl_nontype!(NonMinI8, NonMin, i8, i8::MIN);
+impl_nontype!(NonMinI16, NonMin, i16, i16::MIN);
+impl_nontype!(NonMinI32, NonMin, i32, i32::MIN);
+impl_nontype!(NonMinI64, NonMin, i64, i64::MIN);
+impl_nontype!(NonMinI128, NonMin, i128, i128::MIN);
+impl_nontype!(NonMinIsize, NonMin, isize, isize::MIN);
+
+macro_rules! impl_zero {
+    ($($struct:ident),*) => {
+        $(
+            impl $struct {
+                /// The value zero.
+                pub const ZERO: Self = unsafe { Self::new_unchecked(0) };
             }
-        }
+        )*
+    };
+}
 
-        impl fmt::Display for $struct {
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                <_ as fmt::Display>::fmt(&self.get(), f)
+// Zero is a valid value of every type except `NonMinUX`.
+impl_zero!(
+    NonMaxU8,
+    NonMaxU16,
+    NonMaxU32,
+    NonMaxU64,
+    NonMaxU128,
+    NonMaxUsize
+);
+impl_zero!(
+    NonMaxI8,
+    NonMaxI16,
+    NonMaxI32,
+    NonMaxI64,
+    NonMaxI128,
+    NonMaxIsize
+);
+impl_zero!(
+    NonMinI8,
+    NonMinI16,
+    NonMinI32,
+    NonMinI64,
+    NonMinI128,
+    NonMinIsize
+);
+
+macro_rules! impl_nonzero_repr {
+    ($($struct:ident: $nonzero:ty),*) => {
+        $(
+            impl $struct {
+                /// Returns a reference to the inner value, which equals `self`.
+                #[inline(always)]
+                pub(crate) const fn as_nonzero_repr(&self) -> &$nonzero {
+                    &self.value
+                }
+
+                /// Creates an instance from the inner value, which may be any non-zero value.
+                #[inline(always)]
+                pub(crate) const fn from_nonzero_repr(value: $nonzero) -> Self {
+                    Self { value }
+                }
             }
-        }
-    }
+        )*
+    };
 }
 
-impl_nontype!(NonMaxU8, NonZeroU8, u8, u8::MAX);
-impl_nontype!(NonMaxU16, NonZeroU16, u16, u16::MAX);
-impl_nontype!(NonMaxU32, NonZeroU32, u32, u32::MAX);
-impl_nontype!(NonMaxU64, NonZeroU64, u64, u64::MAX);
-impl_nontype!(NonMaxU128, NonZeroU128, u128, u128::MAX);
-impl_nontype!(NonMaxUsize, NonZeroUsize, usize, usize::MAX);
-
-impl_nontype!(NonMaxI8, NonZeroI8, i8, i8::MAX);
-impl_nontype!(NonMaxI16, NonZeroI16, i16, i16::MAX);
-impl_nontype!(NonMaxI32, NonZeroI32, i32, i32::MAX);
-impl_nontype!(NonMaxI64, NonZeroI64, i64, i64::MAX);
-impl_nontype!(NonMaxI128, NonZeroI128, i128, i128::MAX);
-impl_nontype!(NonMaxIsize, NonZeroIsize, isize, isize::MAX);
-
-impl_nontype!(NonMinU8, NonZeroU8, u8, u8::MIN);
-impl_nontype!(NonMinU16, NonZeroU16, u16, u16::MIN);
-impl_nontype!(NonMinU32, NonZeroU32, u32, u32::MIN);
-impl_nontype!(NonMinU64, NonZeroU64, u64, u64::MIN);
-impl_nontype!(NonMinU128, NonZeroU128, u128, u128::MIN);
-impl_nontype!(NonMinUsize, NonZeroUsize, usize, usize::MIN);
-
-impl_nontype!(NonMinI8, NonZeroI8, i8, i8::MIN);
-impl_nontype!(NonMinI16, NonZeroI16, i16, i16::MIN);
-impl_nontype!(NonMinI32, NonZeroI32, i32, i32::MIN);
-impl_nontype!(NonMinI64, NonZeroI64, i64, i64::MIN);
-impl_nontype!(NonMinI128, NonZeroI128, i128, i128::MIN);
-impl_nontype!(NonMinIsize, NonZeroIsize, isize, isize::MIN);
+// The minimum of an unsigned integer is zero, so `NonMinUX` stores its value unchanged as a
+// `NonZeroUX` in both backends.
+impl_nonzero_repr!(
+    NonMinU8: core::num::NonZeroU8,
+    NonMinU16: core::num::NonZeroU16,
+    NonMinU32: core::num::NonZeroU32,
+    NonMinU64: core::num::NonZeroU64,
+    NonMinU128: core::num::NonZeroU128,
+    NonMinUsize: core::num::NonZeroUsize
+);
 
 #[cfg(test)]
 mod tests {
+    extern crate std;
+
     use super::*;
+    use std::format;
 
     macro_rules! test_nontype {
         ($test_name:ident, $struct:ident, $prim:ident, $mask:expr) => {
@@ -226,12 +626,30 @@ mod tests {
                 let y = $struct::new($mask);
                 assert_eq!(y, None);
 
+                // test conversions.
+                use core::convert::TryFrom;
+                assert_eq!($struct::try_from(val), Ok(x));
+                let kind = if $mask == <$prim>::MAX {
+                    NonMinMaxErrorKind::Max
+                } else {
+                    NonMinMaxErrorKind::Min
+                };
+                assert_eq!($struct::try_from($mask).unwrap_err().kind(), &kind);
+                assert_eq!($prim::from(x), val);
+
                 // test niche filling optimization.
                 use core::mem::size_of;
                 assert_eq!(size_of::<$struct>(), size_of::<$prim>());
                 assert_eq!(size_of::<Option<$struct>>(), size_of::<$prim>());
                 assert_eq!(size_of::<Result<$struct, ()>>(), size_of::<$prim>());
 
+                // test representation, which stores the value unchanged on nightly.
+                let bits = unsafe { core::mem::transmute::<$struct, $prim>(x) };
+                #[cfg(not(feature = "nightly"))]
+                assert_eq!(bits, val ^ $mask);
+                #[cfg(feature = "nightly")]
+                assert_eq!(bits, val);
+
                 // test equality
                 assert_eq!(x, x);
                 assert_ne!(x, $struct::new(42).unwrap());
@@ -240,6 +658,46 @@ mod tests {
                 assert!(x <= x);
                 assert!(!(x < x));
                 assert!($struct::new(1).unwrap() < $struct::new(2).unwrap());
+
+                // test constants
+                assert_eq!($struct::BITS, <$prim>::BITS);
+                assert_eq!($struct::ONE.get(), 1);
+                let min = if $mask == <$prim>::MIN {
+                    <$prim>::MIN + 1
+                } else {
+                    <$prim>::MIN
+                };
+                let max = if $mask == <$prim>::MAX {
+                    <$prim>::MAX - 1
+                } else {
+                    <$prim>::MAX
+                };
+                assert_eq!($struct::MIN.get(), min);
+                assert_eq!($struct::MAX.get(), max);
+                const CONST_X: Option<$struct> = $struct::new(123);
+                const CONST_VAL: $prim = match CONST_X {
+                    Some(x) => x.get(),
+                    None => 0,
+                };
+                assert_eq!(CONST_VAL, val);
+                const CONST_Y: $struct = $struct::new_or_panic(123);
+                assert_eq!(CONST_Y, x);
+
+                // test formatting
+                assert_eq!(format!("{}", x), "123");
+                assert_eq!(format!("{:>5}", x), "  123");
+                assert_eq!(format!("{:b}", x), "1111011");
+                assert_eq!(format!("{:o}", x), "173");
+                assert_eq!(format!("{:x}", x), "7b");
+                assert_eq!(format!("{:#X}", x), "0x7B");
+                assert_eq!(format!("{:e}", x), "1.23e2");
+                assert_eq!(format!("{:E}", x), "1.23E2");
+                assert_eq!(format!("{:?}", x), concat!(stringify!($struct), "(123)"));
+                assert_eq!(format!("{:x?}", x), concat!(stringify!($struct), "(7b)"));
+                assert_eq!(
+                    format!("{:#X?}", x),
+                    concat!(stringify!($struct), "(\n    0x7B,\n)")
+                );
             }
         };
     }
@@ -271,4 +729,77 @@ mod tests {
     test_nontype!(test_nonmini64, NonMinI64, i64, i64::MIN);
     test_nontype!(test_nonmini128, NonMinI128, i128, i128::MIN);
     test_nontype!(test_nonminisize, NonMinIsize, isize, isize::MIN);
+
+    #[test]
+    #[should_panic]
+    fn test_new_or_panic() {
+        NonMaxU8::new_or_panic(u8::MAX);
+    }
+
+    #[test]
+    fn test_generic() {
+        fn roundtrip<T: NicheInt>(value: T) -> (Option<T>, Option<T>) {
+            (
+                NonMax::new(value).map(NonMax::get),
+                NonMin::new(value).map(NonMin::get),
+            )
+        }
+
+        assert_eq!(roundtrip(5u8), (Some(5), Some(5)));
+        assert_eq!(roundtrip(u16::MAX), (None, Some(u16::MAX)));
+        assert_eq!(roundtrip(0u64), (Some(0), None));
+        assert_eq!(roundtrip(i128::MIN), (Some(i128::MIN), None));
+        assert_eq!(roundtrip(isize::MAX), (None, Some(isize::MAX)));
+        assert_eq!(roundtrip(-1i32), (Some(-1), Some(-1)));
+
+        let x: NonMax<i8> = NonMaxI8::new(-3).unwrap();
+        assert_eq!(x, NonMax::new(-3i8).unwrap());
+        assert!(NonMin::new(1u32).unwrap() < NonMin::new(2u32).unwrap());
+    }
+
+    #[test]
+    fn test_generic_traits() {
+        use core::convert::TryInto;
+
+        fn describe<T: NicheInt>(x: NonMax<T>, y: NonMax<T>) -> std::string::String {
+            format!(
+                "{:?} {} {:x} {:?} {} {} {} {}",
+                x,
+                x,
+                x,
+                x.checked_add(y),
+                x + y,
+                x.saturating_add(y),
+                x.wrapping_add(y),
+                x.leading_zeros(),
+            )
+        }
+
+        fn roundtrip<T: NicheInt>(x: NonMax<T>) -> Result<NonMax<T>, NonMinMaxError> {
+            T::from(x).try_into()
+        }
+
+        let x = NonMaxU8::new(100).unwrap();
+        let y = NonMaxU8::new(54).unwrap();
+        assert_eq!(
+            describe(x, y),
+            "NonMaxU8(100) 100 64 Some(NonMaxU8(154)) 154 154 154 1"
+        );
+        assert_eq!(
+            describe(NonMaxI16::new(-3).unwrap(), NonMaxI16::MAX),
+            "NonMaxI16(-3) -3 fffd Some(NonMaxI16(32763)) 32763 32763 32763 0"
+        );
+        assert_eq!(roundtrip(x), Ok(x));
+
+        let min: Result<NonMin<i32>, _> = i32::MIN.try_into();
+        assert_eq!(min.unwrap_err().kind(), &NonMinMaxErrorKind::Min);
+    }
+
+    #[test]
+    fn test_zero() {
+        assert_eq!(NonMaxU8::ZERO.get(), 0);
+        assert_eq!(NonMaxI64::ZERO.get(), 0);
+        assert_eq!(NonMinI32::ZERO.get(), 0);
+        assert_eq!(NonMinIsize::ZERO, NonMinIsize::new(0).unwrap());
+    }
 }
@@ -0,0 +1,106 @@
+//! The `nonmax!` and `nonmin!` macros.
+//!
+//! The macros pick the target type from the type of the constant through the generic `NonMax<T>`
+//! and `NonMin<T>` types, and perform the check in a `const` block.
+
+/// Creates a `NonMaxX` from a constant, where the type is determined by the type of the constant.
+/// Fails to compile if the value equals the maximum value of its type.
+///
+/// ```
+/// # use nonminmax::*;
+/// let x = nonmax!(123u32);
+/// assert_eq!(x, NonMaxU32::new(123).unwrap());
+///
+/// const TABLE: [NonMaxI8; 2] = [nonmax!(-128i8), nonmax!(126i8)];
+/// assert_eq!(TABLE[1].get(), 126);
+/// ```
+///
+/// ```compile_fail
+/// # use nonminmax::*;
+/// let x = nonmax!(255u8);
+/// ```
+#[macro_export]
+macro_rules! nonmax {
+    ($value:expr) => {
+        const {
+            match $crate::NonMax::new($value) {
+                ::core::option::Option::Some(value) => value,
+                ::core::option::Option::None => {
+                    ::core::panic!("value equals the excluded maximum value")
+                }
+            }
+        }
+    };
+}
+
+/// Creates a `NonMinX` from a constant, where the type is determined by the type of the constant.
+/// Fails to compile if the value equals the minimum value of its type.
+///
+/// ```
+/// # use nonminmax::*;
+/// let x = nonmin!(-5i64);
+/// assert_eq!(x, NonMinI64::new(-5).unwrap());
+///
+/// const TABLE: [NonMinU8; 2] = [nonmin!(1u8), nonmin!(255u8)];
+/// assert_eq!(TABLE[0].get(), 1);
+/// ```
+///
+/// ```compile_fail
+/// # use nonminmax::*;
+/// let x = nonmin!(-128i8);
+/// ```
+#[macro_export]
+macro_rules! nonmin {
+    ($value:expr) => {
+        const {
+            match $crate::NonMin::new($value) {
+                ::core::option::Option::Some(value) => value,
+                ::core::option::Option::None => {
+                    ::core::panic!("value equals the excluded minimum value")
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    macro_rules! test_literal {
+        ($test_name:ident, $nonmax:ident, $nonmin:ident, $prim:ident) => {
+            #[test]
+            fn $test_name() {
+                const MAX: $prim = <$prim>::MAX;
+                const MIN: $prim = <$prim>::MIN;
+
+                let x: $nonmax = nonmax!(42 as $prim);
+                assert_eq!(x.get(), 42);
+                assert_eq!(nonmax!(MIN), $nonmax::new(MIN).unwrap());
+                assert_eq!(nonmax!(MAX - 1), $nonmax::new(MAX - 1).unwrap());
+
+                let y: $nonmin = nonmin!(42 as $prim);
+                assert_eq!(y.get(), 42);
+                assert_eq!(nonmin!(MAX), $nonmin::new(MAX).unwrap());
+                assert_eq!(nonmin!(MIN + 1), $nonmin::new(MIN + 1).unwrap());
+
+                assert_eq!(NonMax::new(MAX), None);
+                assert_eq!(NonMin::new(MIN), None);
+            }
+        };
+    }
+
+    test_literal!(test_u8, NonMaxU8, NonMinU8, u8);
+    test_literal!(test_u16, NonMaxU16, NonMinU16, u16);
+    test_literal!(test_u32, NonMaxU32, NonMinU32, u32);
+    test_literal!(test_u64, NonMaxU64, NonMinU64, u64);
+    test_literal!(test_u128, NonMaxU128, NonMinU128, u128);
+    test_literal!(test_usize, NonMaxUsize, NonMinUsize, usize);
+
+    test_literal!(test_i8, NonMaxI8, NonMinI8, i8);
+    test_literal!(test_i16, NonMaxI16, NonMinI16, i16);
+    test_literal!(test_i32, NonMaxI32, NonMinI32, i32);
+    test_literal!(test_i64, NonMaxI64, NonMinI64, i64);
+    test_literal!(test_i128, NonMaxI128, NonMinI128, i128);
+    test_literal!(test_isize, NonMaxIsize, NonMinIsize, isize);
+}
@@ -0,0 +1,319 @@
+//! Arithmetic modulo `2^N - 1` on `N`-bit unsigned words.
+//!
+//! Since `2^N ≡ 1 (mod 2^N - 1)`, a carry out of the top bit can be added back in at the bottom
+//! (the "end-around carry" of ones' complement arithmetic). The functions below accept any word,
+//! but always return a residue in the range `0..2^N - 1`, i.e., never the all-ones word. These are
+//! exactly the values of the corresponding `NonMaxUX` type.
+
+use crate::{NonMaxU128, NonMaxU16, NonMaxU32, NonMaxU64, NonMaxU8, NonMaxUsize};
+use core::fmt;
+use core::iter::{Product, Sum};
+
+/// Provides arithmetic modulo `2^N - 1` on `NonMaxUX` types.
+///
+/// A `NonMaxUX` holds exactly one representative of every residue modulo `2^N - 1`, which is the
+/// ring underlying ones' complement arithmetic. The all-ones word (the "negative zero" of ones'
+/// complement) is mapped to zero. All operations use end-around carry reduction and never need a
+/// division.
+///
+/// For example, the Internet checksum (RFC 1071) of an IPv4 header is the complement of the sum of
+/// its 16-bit words modulo `2^16 - 1`:
+///
+/// ```
+/// # use nonminmax::*;
+/// let words = [
+///     0x4500, 0x0073, 0x0000, 0x4000, 0x4011, 0x0000, 0xc0a8, 0x0001, 0xc0a8, 0x00c7u16,
+/// ];
+///
+/// let sum: ModMersenne<NonMaxU16> = words
+///     .iter()
+///     .copied()
+///     .map(ModMersenne::<NonMaxU16>::reduce)
+///     .sum();
+/// assert_eq!(!sum.0.get(), 0xb861);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct ModMersenne<T>(pub T);
+
+impl<T: fmt::Debug> fmt::Debug for ModMersenne<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for ModMersenne<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+pub trait MersenneWord: Copy {
+    /// Reduces `self` to a residue, mapping the all-ones word to zero.
+    fn reduce(self) -> Self;
+
+    /// Computes `(self + rhs) mod (2^N - 1)`.
+    fn add(self, rhs: Self) -> Self;
+
+    /// Computes `(self - rhs) mod (2^N - 1)`.
+    fn sub(self, rhs: Self) -> Self;
+
+    /// Computes `(self * rhs) mod (2^N - 1)`.
+    fn mul(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_mersenne_word {
+    ($prim:ident, |$lhs:ident, $rhs:ident| $mul:expr) => {
+        impl MersenneWord for $prim {
+            #[inline]
+            fn reduce(self) -> Self {
+                if self == <$prim>::MAX {
+                    0
+                } else {
+                    self
+                }
+            }
+
+            #[inline]
+            fn add(self, rhs: Self) -> Self {
+                // The sum is at most `2^(N + 1) - 2`, so adding back the carry cannot overflow.
+                let (sum, carry) = self.overflowing_add(rhs);
+                (sum + carry as $prim).reduce()
+            }
+
+            #[inline]
+            fn sub(self, rhs: Self) -> Self {
+                // A borrow means `2^N ≡ 1` was added, which is compensated by subtracting one.
+                let (diff, borrow) = self.reduce().overflowing_sub(rhs.reduce());
+                diff - borrow as $prim
+            }
+
+            #[inline]
+            fn mul(self, rhs: Self) -> Self {
+                let ($lhs, $rhs) = (self, rhs);
+                $mul
+            }
+        }
+    };
+    ($prim:ident, $wide:ident) => {
+        impl_mersenne_word!($prim, |lhs, rhs| {
+            // `hi * 2^N + lo ≡ hi + lo`
+            let product = (lhs as $wide) * (rhs as $wide);
+            let hi = (product >> <$prim>::BITS) as $prim;
+            let lo = product as $prim;
+            hi.add(lo)
+        });
+    };
+}
+
+impl_mersenne_word!(u8, u16);
+impl_mersenne_word!(u16, u32);
+impl_mersenne_word!(u32, u64);
+impl_mersenne_word!(u64, u128);
+impl_mersenne_word!(usize, u128);
+impl_mersenne_word!(u128, |lhs, rhs| {
+    // There is no wider type, so split both operands into 64-bit halves. Multiplying by `2^64`
+    // modulo `2^128 - 1` is a rotation by 64 bits.
+    let (l1, l0) = (lhs >> 64, lhs & u64::MAX as u128);
+    let (r1, r0) = (rhs >> 64, rhs & u64::MAX as u128);
+    let cross = (l1 * r0).add(l0 * r1);
+    (l1 * r1).add(l0 * r0).add(cross.rotate_left(64))
+});
+
+macro_rules! impl_mod_mersenne {
+    ($struct:ident, $prim:ident) => {
+        impl ModMersenne<$struct> {
+            doc_comment! {
+                concat!("Reduces an arbitrary `", stringify!($prim), "` modulo `2^", stringify!($prim),
+                "::BITS - 1`. This maps `", stringify!($prim), "::MAX` to zero."),
+                #[inline]
+                pub fn reduce(value: $prim) -> Self {
+                    Self::from_residue(value.reduce())
+                }
+            }
+
+            #[inline]
+            fn residue(self) -> $prim {
+                self.0.get()
+            }
+
+            #[inline]
+            fn from_residue(residue: $prim) -> Self {
+                unsafe { ModMersenne($struct::new_unchecked(residue)) }
+            }
+
+            /// Raises `self` to the power of `exp`, using exponentiation by squaring.
+            #[inline]
+            pub fn pow(self, mut exp: u32) -> Self {
+                let mut base = self.residue();
+                let mut acc = 1;
+
+                while exp > 0 {
+                    if exp & 1 == 1 {
+                        acc = acc.mul(base);
+                    }
+
+                    base = base.mul(base);
+                    exp >>= 1;
+                }
+
+                Self::from_residue(acc)
+            }
+
+            /// Returns the multiplicative inverse of `self`, or `None` if `self` is not coprime to
+            /// the modulus (which includes zero).
+            pub fn inv(self) -> Option<Self> {
+                // Extended Euclidean algorithm, where the Bézout coefficients are kept as residues.
+                // The invariant is that `t * self ≡ r` for both pairs.
+                let (mut r0, mut r1) = (<$prim>::MAX, self.residue());
+                let (mut t0, mut t1): ($prim, $prim) = (0, 1);
+
+                while r1 != 0 {
+                    let q = r0 / r1;
+                    (r0, r1) = (r1, r0 - q * r1);
+                    (t0, t1) = (t1, t0.sub(q.reduce().mul(t1)));
+                }
+
+                if r0 == 1 {
+                    Some(Self::from_residue(t0))
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl core::ops::Neg for ModMersenne<$struct> {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                Self::from_residue(0.sub(self.residue()))
+            }
+        }
+
+        impl_mod_mersenne_binop!($struct, Add, add, AddAssign, add_assign);
+        impl_mod_mersenne_binop!($struct, Sub, sub, SubAssign, sub_assign);
+        impl_mod_mersenne_binop!($struct, Mul, mul, MulAssign, mul_assign);
+
+        impl Sum for ModMersenne<$struct> {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::from_residue(0), |lhs, rhs| lhs + rhs)
+            }
+        }
+
+        impl<'a> Sum<&'a Self> for ModMersenne<$struct> {
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.copied().sum()
+            }
+        }
+
+        impl Product for ModMersenne<$struct> {
+            fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::from_residue(1), |lhs, rhs| lhs * rhs)
+            }
+        }
+
+        impl<'a> Product<&'a Self> for ModMersenne<$struct> {
+            fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.copied().product()
+            }
+        }
+    };
+}
+
+macro_rules! impl_mod_mersenne_binop {
+    ($struct:ident, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        impl core::ops::$trait for ModMersenne<$struct> {
+            type Output = Self;
+
+            #[inline]
+            fn $method(self, rhs: Self) -> Self {
+                Self::from_residue(MersenneWord::$method(self.residue(), rhs.residue()))
+            }
+        }
+
+        impl core::ops::$assign_trait for ModMersenne<$struct> {
+            #[inline]
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = <Self as core::ops::$trait>::$method(*self, rhs);
+            }
+        }
+    };
+}
+
+impl_mod_mersenne!(NonMaxU8, u8);
+impl_mod_mersenne!(NonMaxU16, u16);
+impl_mod_mersenne!(NonMaxU32, u32);
+impl_mod_mersenne!(NonMaxU64, u64);
+impl_mod_mersenne!(NonMaxU128, u128);
+impl_mod_mersenne!(NonMaxUsize, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::{MersenneWord, ModMersenne};
+    use crate::*;
+
+    #[test]
+    fn test_u8_exhaustive() {
+        for a in 0..=u8::MAX {
+            for b in 0..=u8::MAX {
+                let (x, y) = (a as u32, b as u32);
+                assert_eq!(a.add(b) as u32, (x + y) % 255);
+                assert_eq!(a.sub(b) as u32, (x % 255 + 255 - y % 255) % 255);
+                assert_eq!(a.mul(b) as u32, (x * y) % 255);
+            }
+        }
+    }
+
+    #[test]
+    fn test_u128() {
+        let m = u128::MAX;
+        assert_eq!((m - 1).add(1), 0);
+        assert_eq!(0u128.sub(1), m - 1);
+        assert_eq!((m - 1).mul(m - 1), 1);
+        assert_eq!((1u128 << 127).mul(2), 1);
+        assert_eq!(
+            (u64::MAX as u128).mul(u64::MAX as u128),
+            u64::MAX as u128 * u64::MAX as u128
+        );
+        assert_eq!(((1u128 << 100) + 3).mul(1 << 30), (1 << 2) + (3 << 30));
+    }
+
+    #[test]
+    fn test_mod_mersenne_u8() {
+        let m = |x: u8| ModMersenne::<NonMaxU8>::reduce(x);
+
+        assert_eq!(m(255), m(0));
+        assert_eq!(m(200) + m(100), m(45));
+        assert_eq!(m(100) - m(200), m(155));
+        assert_eq!(-m(1), m(254));
+        assert_eq!(-m(0), m(0));
+        assert_eq!(m(3).pow(5), m(243));
+        assert_eq!(m(2).pow(8), m(1));
+        assert_eq!([m(1), m(2), m(3)].iter().sum::<ModMersenne<_>>(), m(6));
+        assert_eq!([m(1), m(2), m(3)].iter().product::<ModMersenne<_>>(), m(6));
+
+        // 255 = 3 * 5 * 17, so only values coprime to it are invertible.
+        for x in 0..=254 {
+            match m(x).inv() {
+                Some(y) => assert_eq!(m(x) * y, m(1)),
+                None => assert!(x % 3 == 0 || x % 5 == 0 || x % 17 == 0),
+            }
+        }
+    }
+
+    #[test]
+    fn test_mod_mersenne_wide() {
+        let x = ModMersenne(NonMaxU64::new(0x1234_5678_9abc_def1).unwrap());
+        assert_eq!(
+            x * x.inv().unwrap(),
+            ModMersenne(NonMaxU64::new(1).unwrap())
+        );
+        assert_eq!(x - x, ModMersenne(NonMaxU64::new(0).unwrap()));
+
+        let y = ModMersenne(NonMaxU128::new(u128::MAX - 1).unwrap());
+        assert_eq!(y * y, ModMersenne(NonMaxU128::new(1).unwrap()));
+        assert_eq!(y.inv(), Some(y));
+        assert_eq!(ModMersenne::<NonMaxU128>::reduce(u128::MAX).inv(), None);
+    }
+}
@@ -0,0 +1,322 @@
+//! The `NicheInt` trait, which is implemented for the primitive integers that can be used with
+//! `NonMax<T>` and `NonMin<T>`.
+//!
+//! The generic types store `value ^ mask` in the `NonZeroX` type of the same width. Trait methods
+//! cannot be called in a `const fn`, so the generic `const` methods instead operate on the raw
+//! bits of the values, which are dispatched on the size of the type. The other methods of the
+//! generic types use the primitive operations of the sealed supertrait.
+//!
+//! With the `nightly` feature, the generic types instead store the value unchanged in a pattern
+//! type which declares the valid range, so `get` and `new_unchecked` are no-ops. The
+//! `rustc_layout_scalar_valid_range_start/end` attributes would serve the same purpose, but are not
+//! available on recent nightly compilers.
+
+use crate::{NonMax, NonMin, NonMinMaxError};
+use core::convert::TryInto;
+use core::fmt;
+use core::hash::Hash;
+use core::mem::size_of;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use core::ops::{BitAnd, BitOr, BitXor, Div, Rem};
+
+pub(crate) mod sealed {
+    use super::*;
+    use crate::mersenne::MersenneWord;
+
+    /// The primitive operations which the methods of `NonMax<T>`/`NonMin<T>` are built on.
+    pub trait Sealed:
+        Sized
+        + Div<Output = Self>
+        + Rem<Output = Self>
+        + BitAnd<Output = Self>
+        + BitOr<Output = Self>
+        + BitXor<Output = Self>
+    {
+        /// The unsigned integer type of the same width.
+        type Unsigned: MersenneWord;
+
+        /// The `nightly` backend stores the value of `NonMax<T>`/`NonMin<T>` unchanged in a type
+        /// which declares the valid range, with `BELOW_MAX` and `ABOVE_MIN` as its bounds.
+        #[cfg(feature = "nightly")]
+        type NonMaxRepr: Copy;
+        #[cfg(feature = "nightly")]
+        type NonMinRepr: Copy;
+
+        const ZERO: Self;
+        const ONE: Self;
+        const BELOW_MAX: Self;
+        const ABOVE_MIN: Self;
+
+        /// The names of the `NonMaxX`/`NonMinX` aliases, which are used by `Debug`.
+        const NON_MAX_NAME: &'static str;
+        const NON_MIN_NAME: &'static str;
+
+        /// Casts `self` to the unsigned integer type of the same width.
+        fn to_unsigned(self) -> Self::Unsigned;
+
+        /// Casts `value` from the unsigned integer type of the same width.
+        fn from_unsigned(value: Self::Unsigned) -> Self;
+
+        fn checked_add(self, rhs: Self) -> Option<Self>;
+        fn checked_sub(self, rhs: Self) -> Option<Self>;
+        fn checked_mul(self, rhs: Self) -> Option<Self>;
+        fn checked_div(self, rhs: Self) -> Option<Self>;
+        fn checked_rem(self, rhs: Self) -> Option<Self>;
+        fn checked_neg(self) -> Option<Self>;
+        fn checked_pow(self, exp: u32) -> Option<Self>;
+        fn checked_shl(self, rhs: u32) -> Option<Self>;
+        fn checked_shr(self, rhs: u32) -> Option<Self>;
+        fn saturating_add(self, rhs: Self) -> Self;
+        fn saturating_sub(self, rhs: Self) -> Self;
+        fn saturating_mul(self, rhs: Self) -> Self;
+        fn saturating_pow(self, exp: u32) -> Self;
+        fn wrapping_add(self, rhs: Self) -> Self;
+        fn wrapping_sub(self, rhs: Self) -> Self;
+        fn leading_zeros(self) -> u32;
+        fn trailing_zeros(self) -> u32;
+        fn checked_ilog2(self) -> Option<u32>;
+        fn checked_ilog10(self) -> Option<u32>;
+    }
+}
+
+/// A primitive integer type which can be used with `NonMax<T>` and `NonMin<T>`.
+///
+/// This trait is sealed and cannot be implemented outside of this crate. The conversions between
+/// `T` and `NonMax<T>`/`NonMin<T>` are available in generic code through the `From` and `TryInto`
+/// supertraits.
+///
+/// ```
+/// # use nonminmax::*;
+/// use core::convert::TryInto;
+///
+/// fn sum<T: NicheInt + Into<u128>>(values: &[NonMax<T>]) -> u128 {
+///     values.iter().map(|&v| T::from(v).into()).sum()
+/// }
+///
+/// fn parse<T: NicheInt>(value: T) -> Option<NonMax<T>> {
+///     value.try_into().ok()
+/// }
+///
+/// assert_eq!(sum(&[NonMaxU8::new(1).unwrap(), NonMaxU8::new(2).unwrap()]), 3);
+/// assert_eq!(sum(&[NonMaxU64::new(3).unwrap()]), 3);
+/// assert_eq!(parse(u8::MAX), None);
+/// ```
+pub trait NicheInt:
+    Copy
+    + Eq
+    + Ord
+    + Hash
+    + fmt::Debug
+    + fmt::Display
+    + fmt::Binary
+    + fmt::Octal
+    + fmt::LowerHex
+    + fmt::UpperHex
+    + fmt::LowerExp
+    + fmt::UpperExp
+    + From<NonMax<Self>>
+    + From<NonMin<Self>>
+    + TryInto<NonMax<Self>, Error = NonMinMaxError>
+    + TryInto<NonMin<Self>, Error = NonMinMaxError>
+    + sealed::Sealed
+    + 'static
+{
+    /// The `NonZeroX` type of the same width, which stores the encoded value.
+    type NonZero: Copy + Eq + Hash + fmt::Debug;
+
+    /// The smallest value of this integer type.
+    const MIN: Self;
+
+    /// The largest value of this integer type.
+    const MAX: Self;
+}
+
+/// Implements the methods of `Sealed` by calling the inherent methods of the primitive.
+macro_rules! forward_prim_ops {
+    ($prim:ident, $(fn $method:ident(self $(, $arg:ident: $ty:ty)?) -> $ret:ty;)*) => {
+        $(
+            #[inline]
+            fn $method(self $(, $arg: $ty)?) -> $ret {
+                <$prim>::$method(self $(, $arg)?)
+            }
+        )*
+    };
+}
+
+macro_rules! impl_niche_int {
+    ($prim:ident, $nonzero:ident, $unsigned:ident, $nonmax:ident, $nonmin:ident, unsigned) => {
+        // The mask of `NonMinUX` is zero, so it stores the value unchanged in both backends.
+        impl_niche_int!(@impl $prim, $nonzero, $unsigned, $nonmax, $nonmin, $nonzero);
+    };
+    ($prim:ident, $nonzero:ident, $unsigned:ident, $nonmax:ident, $nonmin:ident, signed) => {
+        impl_niche_int!(
+            @impl $prim,
+            $nonzero,
+            $unsigned,
+            $nonmax,
+            $nonmin,
+            core::pattern_type!($prim is <$prim as sealed::Sealed>::ABOVE_MIN..=<$prim>::MAX)
+        );
+    };
+    (@impl $prim:ident, $nonzero:ident, $unsigned:ident, $nonmax:ident, $nonmin:ident, $nonmin_repr:ty) => {
+        impl sealed::Sealed for $prim {
+            type Unsigned = $unsigned;
+            #[cfg(feature = "nightly")]
+            type NonMaxRepr = core::pattern_type!(
+                $prim is <$prim>::MIN..=<$prim as sealed::Sealed>::BELOW_MAX
+            );
+            #[cfg(feature = "nightly")]
+            type NonMinRepr = $nonmin_repr;
+
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const BELOW_MAX: Self = <$prim>::MAX - 1;
+            const ABOVE_MIN: Self = <$prim>::MIN + 1;
+            const NON_MAX_NAME: &'static str = stringify!($nonmax);
+            const NON_MIN_NAME: &'static str = stringify!($nonmin);
+
+            #[inline]
+            fn to_unsigned(self) -> $unsigned {
+                self as $unsigned
+            }
+
+            #[inline]
+            fn from_unsigned(value: $unsigned) -> Self {
+                value as $prim
+            }
+
+            forward_prim_ops! {
+                $prim,
+                fn checked_add(self, rhs: Self) -> Option<Self>;
+                fn checked_sub(self, rhs: Self) -> Option<Self>;
+                fn checked_mul(self, rhs: Self) -> Option<Self>;
+                fn checked_div(self, rhs: Self) -> Option<Self>;
+                fn checked_rem(self, rhs: Self) -> Option<Self>;
+                fn checked_neg(self) -> Option<Self>;
+                fn checked_pow(self, exp: u32) -> Option<Self>;
+                fn checked_shl(self, rhs: u32) -> Option<Self>;
+                fn checked_shr(self, rhs: u32) -> Option<Self>;
+                fn saturating_add(self, rhs: Self) -> Self;
+                fn saturating_sub(self, rhs: Self) -> Self;
+                fn saturating_mul(self, rhs: Self) -> Self;
+                fn saturating_pow(self, exp: u32) -> Self;
+                fn wrapping_add(self, rhs: Self) -> Self;
+                fn wrapping_sub(self, rhs: Self) -> Self;
+                fn leading_zeros(self) -> u32;
+                fn trailing_zeros(self) -> u32;
+                fn checked_ilog2(self) -> Option<u32>;
+                fn checked_ilog10(self) -> Option<u32>;
+            }
+        }
+
+        impl NicheInt for $prim {
+            type NonZero = $nonzero;
+            const MIN: Self = <$prim>::MIN;
+            const MAX: Self = <$prim>::MAX;
+        }
+    };
+}
+
+impl_niche_int!(u8, NonZeroU8, u8, NonMaxU8, NonMinU8, unsigned);
+impl_niche_int!(u16, NonZeroU16, u16, NonMaxU16, NonMinU16, unsigned);
+impl_niche_int!(u32, NonZeroU32, u32, NonMaxU32, NonMinU32, unsigned);
+impl_niche_int!(u64, NonZeroU64, u64, NonMaxU64, NonMinU64, unsigned);
+impl_niche_int!(u128, NonZeroU128, u128, NonMaxU128, NonMinU128, unsigned);
+impl_niche_int!(
+    usize,
+    NonZeroUsize,
+    usize,
+    NonMaxUsize,
+    NonMinUsize,
+    unsigned
+);
+
+impl_niche_int!(i8, NonZeroI8, u8, NonMaxI8, NonMinI8, signed);
+impl_niche_int!(i16, NonZeroI16, u16, NonMaxI16, NonMinI16, signed);
+impl_niche_int!(i32, NonZeroI32, u32, NonMaxI32, NonMinI32, signed);
+impl_niche_int!(i64, NonZeroI64, u64, NonMaxI64, NonMinI64, signed);
+impl_niche_int!(i128, NonZeroI128, u128, NonMaxI128, NonMinI128, signed);
+impl_niche_int!(isize, NonZeroIsize, usize, NonMaxIsize, NonMinIsize, signed);
+
+/// Reinterprets the bits of `value` as a `B`.
+///
+/// # Safety
+/// `A` and `B` must have the same size and the bits of `value` must be a valid `B`.
+const unsafe fn pun<A: Copy, B: Copy>(value: A) -> B {
+    union Pun<A: Copy, B: Copy> {
+        a: A,
+        b: B,
+    }
+
+    Pun { a: value }.b
+}
+
+/// Returns the bits of `value`, zero-extended to 128 bits.
+///
+/// # Safety
+/// The size of `T` must be the size of one of the primitive integers.
+const unsafe fn to_bits<T: Copy>(value: T) -> u128 {
+    match size_of::<T>() {
+        1 => pun::<T, u8>(value) as u128,
+        2 => pun::<T, u16>(value) as u128,
+        4 => pun::<T, u32>(value) as u128,
+        8 => pun::<T, u64>(value) as u128,
+        16 => pun::<T, u128>(value),
+        _ => unreachable!(),
+    }
+}
+
+/// Reinterprets the lowest bits of `bits` as a `T`.
+///
+/// # Safety
+/// The size of `T` must be the size of one of the primitive integers, and the lowest bits must be
+/// a valid `T`.
+#[cfg(not(feature = "nightly"))]
+const unsafe fn from_bits<T: Copy>(bits: u128) -> T {
+    match size_of::<T>() {
+        1 => pun::<u8, T>(bits as u8),
+        2 => pun::<u16, T>(bits as u16),
+        4 => pun::<u32, T>(bits as u32),
+        8 => pun::<u64, T>(bits as u64),
+        16 => pun::<u128, T>(bits),
+        _ => unreachable!(),
+    }
+}
+
+/// Returns whether `a` and `b` are equal.
+pub(crate) const fn eq<T: NicheInt>(a: T, b: T) -> bool {
+    unsafe { to_bits(a) == to_bits(b) }
+}
+
+/// Returns `value ^ mask` as a `T::NonZero`.
+///
+/// # Safety
+/// The value cannot be equal to `mask`.
+#[cfg(not(feature = "nightly"))]
+pub(crate) const unsafe fn encode<T: NicheInt>(value: T, mask: T) -> T::NonZero {
+    from_bits(to_bits(value) ^ to_bits(mask))
+}
+
+/// Returns `value ^ mask` as a `T`. This is the inverse of `encode`.
+#[cfg(not(feature = "nightly"))]
+pub(crate) const fn decode<T: NicheInt>(value: T::NonZero, mask: T) -> T {
+    unsafe { from_bits(to_bits(value) ^ to_bits(mask)) }
+}
+
+/// Returns `value` as a `R` with the same bits, which is used by the `nightly` backend.
+///
+/// # Safety
+/// `R` must have the same size as `T`, and `value` must lie in the valid range of `R`.
+#[cfg(feature = "nightly")]
+pub(crate) const unsafe fn store<T: NicheInt, R: Copy>(value: T) -> R {
+    pun(value)
+}
+
+/// Returns `value` as a `T` with the same bits. This is the inverse of `store`.
+#[cfg(feature = "nightly")]
+pub(crate) const fn load<T: NicheInt, R: Copy>(value: R) -> T {
+    unsafe { pun(value) }
+}
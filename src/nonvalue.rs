@@ -0,0 +1,293 @@
+//! Integer types which exclude an arbitrary value `V`, given as a const generic parameter.
+//!
+//! These types store `value ^ V` in the `NonZeroX` type of the same width, like `NonMaxX` and
+//! `NonMinX` store `value ^ MAX` and `value ^ MIN`. Hence, `NonValueX<{ X::MAX }>` has the same
+//! representation as `NonMaxX` and converts into it for free, and likewise for `NonMinX`. With the
+//! `nightly` feature, `NonMaxX` and `NonMinX` store the value unchanged instead, so these
+//! conversions cost a single `xor`.
+
+use crate::error::{NonMinMaxError, NonMinMaxErrorKind};
+use crate::{NonMaxI128, NonMaxI16, NonMaxI32, NonMaxI64, NonMaxI8, NonMaxIsize};
+use crate::{NonMaxU128, NonMaxU16, NonMaxU32, NonMaxU64, NonMaxU8, NonMaxUsize};
+use crate::{NonMinI128, NonMinI16, NonMinI32, NonMinI64, NonMinI8, NonMinIsize};
+use crate::{NonMinU128, NonMinU16, NonMinU32, NonMinU64, NonMinU8, NonMinUsize};
+use core::fmt;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+
+macro_rules! impl_nonvalue {
+    ($struct:ident, $nonzero:ident, $prim:ident, $nonmax:ident, $nonmin:ident, $example:expr) => {
+        doc_comment! {
+            concat!("An integer of type `", stringify!($prim), "` which is known to not equal `V`.
+
+Like the other types, this type allows for the niche filling optimization, meaning that
+`Option<", stringify!($struct), "<V>>` takes up the same amount of space as `", stringify!($prim), "`.
+
+```
+# use nonminmax::*;
+type Id = ", stringify!($struct), "<", $example, ">;
+
+let x = Id::new(123).unwrap();
+assert_eq!(x.get(), 123);
+assert_eq!(Id::new(", $example, "), None);
+
+use std::mem::size_of;
+assert_eq!(size_of::<Option<Id>>(), size_of::<", stringify!($prim), ">());
+```"),
+            #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+            #[repr(transparent)]
+            pub struct $struct<const V: $prim> {
+                value: $nonzero,
+            }
+        }
+
+        impl<const V: $prim> $struct<V> {
+            /// The excluded value.
+            pub const EXCLUDED: $prim = V;
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($struct), "` by checking if the value is not `V`."),
+                #[inline(always)]
+                pub const fn new(value: $prim) -> Option<Self> {
+                    if value != V {
+                        unsafe { Some(Self::new_unchecked(value)) }
+                    } else {
+                        None
+                    }
+                }
+            }
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($struct), "`, or panics if the value is `V`.\n",
+                " # Panics\n",
+                "Panics if the value is equal to `V`. In a `const` context, this results in a ",
+                "compile-time error."),
+                #[inline]
+                #[track_caller]
+                pub const fn new_or_panic(value: $prim) -> Self {
+                    match Self::new(value) {
+                        Some(value) => value,
+                        None => panic!("value equals `V`"),
+                    }
+                }
+            }
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($struct), "` without checking if the value is not `V`.\n",
+                " # Safety\n",
+                "The value cannot be equal to `V`."),
+                #[inline(always)]
+                pub const unsafe fn new_unchecked(value: $prim) -> Self {
+                    let value = $nonzero::new_unchecked(value ^ V);
+
+                    Self { value }
+                }
+            }
+
+            /// Returns the integer value.
+            #[inline(always)]
+            pub const fn get(self) -> $prim {
+                self.value.get() ^ V
+            }
+        }
+
+        impl<const V: $prim> From<$struct<V>> for $prim {
+            fn from(nontype: $struct<V>) -> Self {
+                nontype.get()
+            }
+        }
+
+        impl<const V: $prim> core::convert::TryFrom<$prim> for $struct<V> {
+            type Error = NonMinMaxError;
+
+            fn try_from(value: $prim) -> Result<Self, Self::Error> {
+                Self::new(value).ok_or_else(|| NonMinMaxError::new(NonMinMaxErrorKind::Value))
+            }
+        }
+
+        impl<const V: $prim> PartialOrd for $struct<V> {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<const V: $prim> Ord for $struct<V> {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.get().cmp(&other.get())
+            }
+        }
+
+        impl<const V: $prim> fmt::Debug for $struct<V> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($struct)).field(&self.get()).finish()
+            }
+        }
+
+        impl_fmt!([const V: $prim] $struct<V>, Display, Binary, Octal, LowerHex, UpperHex, LowerExp, UpperExp);
+
+        impl From<$nonmax> for $struct<{ <$prim>::MAX }> {
+            #[inline]
+            fn from(value: $nonmax) -> Self {
+                // Both types exclude `MAX`. Without the `nightly` feature, both types store
+                // `value ^ MAX`, so this compiles to a no-op.
+                unsafe { Self::new_unchecked(value.get()) }
+            }
+        }
+
+        impl From<$struct<{ <$prim>::MAX }>> for $nonmax {
+            #[inline]
+            fn from(value: $struct<{ <$prim>::MAX }>) -> Self {
+                unsafe { Self::new_unchecked(value.get()) }
+            }
+        }
+
+        impl From<$nonmin> for $struct<{ <$prim>::MIN }> {
+            #[inline]
+            fn from(value: $nonmin) -> Self {
+                // Both types exclude `MIN`. Without the `nightly` feature, both types store
+                // `value ^ MIN`, so this compiles to a no-op.
+                unsafe { Self::new_unchecked(value.get()) }
+            }
+        }
+
+        impl From<$struct<{ <$prim>::MIN }>> for $nonmin {
+            #[inline]
+            fn from(value: $struct<{ <$prim>::MIN }>) -> Self {
+                unsafe { Self::new_unchecked(value.get()) }
+            }
+        }
+    };
+}
+
+impl_nonvalue!(NonValueU8, NonZeroU8, u8, NonMaxU8, NonMinU8, "0xAD");
+impl_nonvalue!(NonValueU16, NonZeroU16, u16, NonMaxU16, NonMinU16, "0xDEAD");
+impl_nonvalue!(NonValueU32, NonZeroU32, u32, NonMaxU32, NonMinU32, "0xDEAD");
+impl_nonvalue!(NonValueU64, NonZeroU64, u64, NonMaxU64, NonMinU64, "0xDEAD");
+impl_nonvalue!(
+    NonValueU128,
+    NonZeroU128,
+    u128,
+    NonMaxU128,
+    NonMinU128,
+    "0xDEAD"
+);
+impl_nonvalue!(
+    NonValueUsize,
+    NonZeroUsize,
+    usize,
+    NonMaxUsize,
+    NonMinUsize,
+    "0xDEAD"
+);
+
+impl_nonvalue!(NonValueI8, NonZeroI8, i8, NonMaxI8, NonMinI8, "-1");
+impl_nonvalue!(NonValueI16, NonZeroI16, i16, NonMaxI16, NonMinI16, "-1");
+impl_nonvalue!(NonValueI32, NonZeroI32, i32, NonMaxI32, NonMinI32, "-1");
+impl_nonvalue!(NonValueI64, NonZeroI64, i64, NonMaxI64, NonMinI64, "-1");
+impl_nonvalue!(
+    NonValueI128,
+    NonZeroI128,
+    i128,
+    NonMaxI128,
+    NonMinI128,
+    "-1"
+);
+impl_nonvalue!(
+    NonValueIsize,
+    NonZeroIsize,
+    isize,
+    NonMaxIsize,
+    NonMinIsize,
+    "-1"
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use core::convert::TryFrom;
+
+    macro_rules! test_nonvalue {
+        ($test_name:ident, $struct:ident, $nonmax:ident, $nonmin:ident, $prim:ident, $value:expr) => {
+            #[test]
+            fn $test_name() {
+                type T = $struct<$value>;
+
+                for &value in &[0, 1, 42, <$prim>::MAX, <$prim>::MIN] {
+                    let x = T::new(value).unwrap();
+                    assert_eq!(x.get(), value);
+                    assert_eq!(T::try_from(value), Ok(x));
+                    assert_eq!(<$prim>::from(x), value);
+                }
+
+                assert_eq!(T::new($value), None);
+                assert_eq!(T::EXCLUDED, $value);
+                assert_eq!(
+                    T::try_from($value).unwrap_err().kind(),
+                    &NonMinMaxErrorKind::Value
+                );
+                assert!(T::new(0).unwrap() < T::new(1).unwrap());
+
+                use core::mem::size_of;
+                assert_eq!(size_of::<Option<T>>(), size_of::<$prim>());
+
+                let x = $nonmax::new(42).unwrap();
+                let y = $struct::<{ <$prim>::MAX }>::from(x);
+                assert_eq!(y.get(), 42);
+                assert_eq!($nonmax::from(y), x);
+
+                let x = $nonmin::new(42).unwrap();
+                let y = $struct::<{ <$prim>::MIN }>::from(x);
+                assert_eq!(y.get(), 42);
+                assert_eq!($nonmin::from(y), x);
+            }
+        };
+    }
+
+    test_nonvalue!(test_u8, NonValueU8, NonMaxU8, NonMinU8, u8, 0xAD);
+    test_nonvalue!(test_u16, NonValueU16, NonMaxU16, NonMinU16, u16, 0xDEAD);
+    test_nonvalue!(test_u32, NonValueU32, NonMaxU32, NonMinU32, u32, 0xDEAD);
+    test_nonvalue!(test_u64, NonValueU64, NonMaxU64, NonMinU64, u64, 0xDEAD);
+    test_nonvalue!(
+        test_u128,
+        NonValueU128,
+        NonMaxU128,
+        NonMinU128,
+        u128,
+        0xDEAD
+    );
+    test_nonvalue!(
+        test_usize,
+        NonValueUsize,
+        NonMaxUsize,
+        NonMinUsize,
+        usize,
+        0xDEAD
+    );
+
+    test_nonvalue!(test_i8, NonValueI8, NonMaxI8, NonMinI8, i8, -7);
+    test_nonvalue!(test_i16, NonValueI16, NonMaxI16, NonMinI16, i16, -7);
+    test_nonvalue!(test_i32, NonValueI32, NonMaxI32, NonMinI32, i32, -7);
+    test_nonvalue!(test_i64, NonValueI64, NonMaxI64, NonMinI64, i64, -7);
+    test_nonvalue!(test_i128, NonValueI128, NonMaxI128, NonMinI128, i128, -7);
+    test_nonvalue!(
+        test_isize,
+        NonValueIsize,
+        NonMaxIsize,
+        NonMinIsize,
+        isize,
+        -7
+    );
+
+    #[test]
+    fn test_format() {
+        extern crate std;
+        use std::format;
+
+        let x = NonValueU32::<0xDEAD>::new(255).unwrap();
+        assert_eq!(format!("{:?}", x), "NonValueU32(255)");
+        assert_eq!(format!("{:#x}", x), "0xff");
+        assert_eq!(format!("{}", x), "255");
+    }
+}
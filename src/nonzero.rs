@@ -0,0 +1,71 @@
+//! Conversions between `NonMinUX` and `core::num::NonZeroUX`.
+//!
+//! The minimum of an unsigned integer is zero, so the mask of `NonMinUX` is zero and its inner
+//! value is stored unchanged. Hence, both types have exactly the same values and representation,
+//! and these conversions are free.
+
+use crate::{NonMinU128, NonMinU16, NonMinU32, NonMinU64, NonMinU8, NonMinUsize};
+use core::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize};
+
+macro_rules! impl_nonzero {
+    ($struct:ident, $nonzero:ident) => {
+        impl $struct {
+            doc_comment! {
+                concat!("Returns a reference to `self` as a `", stringify!($nonzero), "`."),
+                #[inline]
+                pub fn as_nonzero(&self) -> &$nonzero {
+                    self.as_nonzero_repr()
+                }
+            }
+        }
+
+        impl From<$nonzero> for $struct {
+            #[inline]
+            fn from(value: $nonzero) -> Self {
+                Self::from_nonzero_repr(value)
+            }
+        }
+
+        impl From<$struct> for $nonzero {
+            #[inline]
+            fn from(value: $struct) -> Self {
+                *value.as_nonzero_repr()
+            }
+        }
+    };
+}
+
+impl_nonzero!(NonMinU8, NonZeroU8);
+impl_nonzero!(NonMinU16, NonZeroU16);
+impl_nonzero!(NonMinU32, NonZeroU32);
+impl_nonzero!(NonMinU64, NonZeroU64);
+impl_nonzero!(NonMinU128, NonZeroU128);
+impl_nonzero!(NonMinUsize, NonZeroUsize);
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use core::num::*;
+
+    macro_rules! test_nonzero {
+        ($test_name:ident, $struct:ident, $nonzero:ident, $prim:ident) => {
+            #[test]
+            fn $test_name() {
+                for &value in &[1, 2, 42, <$prim>::MAX - 1, <$prim>::MAX] {
+                    let x = $struct::new(value).unwrap();
+                    let y = $nonzero::new(value).unwrap();
+                    assert_eq!(x.as_nonzero(), &y);
+                    assert_eq!($nonzero::from(x), y);
+                    assert_eq!($struct::from(y), x);
+                }
+            }
+        };
+    }
+
+    test_nonzero!(test_u8, NonMinU8, NonZeroU8, u8);
+    test_nonzero!(test_u16, NonMinU16, NonZeroU16, u16);
+    test_nonzero!(test_u32, NonMinU32, NonZeroU32, u32);
+    test_nonzero!(test_u64, NonMinU64, NonZeroU64, u64);
+    test_nonzero!(test_u128, NonMinU128, NonZeroU128, u128);
+    test_nonzero!(test_usize, NonMinUsize, NonZeroUsize, usize);
+}
@@ -0,0 +1,251 @@
+//! Checked arithmetic and the arithmetic operator traits.
+//!
+//! The operators behave like their primitive counterparts, except that they also panic when the
+//! result would be the excluded value, with the message "result equals the excluded value". Unlike
+//! the primitives, the checks are performed in both debug and release builds since wrapping could
+//! otherwise produce an invalid value.
+//!
+//! The macros take the generic parameters of the impls in brackets, so they are implemented once
+//! for `NonMax<T>`/`NonMin<T>` as well as for concrete types such as `NonExtremeIX`.
+
+macro_rules! impl_checked_ops {
+    ($struct:ident, $prim:ident) => {
+        impl_checked_ops!([] $struct, $prim);
+    };
+    ([$($gen:tt)*] $struct:ty, $prim:ty) => {
+        impl<$($gen)*> $struct {
+            doc_comment! {
+                concat!("Checked integer addition. Computes `self + rhs`, returning `None` if ",
+                "overflow occurred or if the result is not a valid `", stringify!($struct), "`."),
+                #[inline]
+                pub fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$prim>::checked_add(self.get(), rhs.get()).and_then(Self::new)
+                }
+            }
+
+            doc_comment! {
+                concat!("Checked integer subtraction. Computes `self - rhs`, returning `None` if ",
+                "overflow occurred or if the result is not a valid `", stringify!($struct), "`."),
+                #[inline]
+                pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$prim>::checked_sub(self.get(), rhs.get()).and_then(Self::new)
+                }
+            }
+
+            doc_comment! {
+                concat!("Checked integer multiplication. Computes `self * rhs`, returning `None` if ",
+                "overflow occurred or if the result is not a valid `", stringify!($struct), "`."),
+                #[inline]
+                pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$prim>::checked_mul(self.get(), rhs.get()).and_then(Self::new)
+                }
+            }
+
+            doc_comment! {
+                concat!("Checked integer division. Computes `self / rhs`, returning `None` if ",
+                "`rhs == 0`, overflow occurred or if the result is not a valid `", stringify!($struct), "`."),
+                #[inline]
+                pub fn checked_div(self, rhs: Self) -> Option<Self> {
+                    <$prim>::checked_div(self.get(), rhs.get()).and_then(Self::new)
+                }
+            }
+
+            doc_comment! {
+                concat!("Checked integer remainder. Computes `self % rhs`, returning `None` if ",
+                "`rhs == 0`, overflow occurred or if the result is not a valid `", stringify!($struct), "`."),
+                #[inline]
+                pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+                    <$prim>::checked_rem(self.get(), rhs.get()).and_then(Self::new)
+                }
+            }
+
+            doc_comment! {
+                concat!("Checked negation. Computes `-self`, returning `None` if overflow ",
+                "occurred or if the result is not a valid `", stringify!($struct), "`."),
+                #[inline]
+                pub fn checked_neg(self) -> Option<Self> {
+                    <$prim>::checked_neg(self.get()).and_then(Self::new)
+                }
+            }
+
+            doc_comment! {
+                concat!("Checked exponentiation. Computes `self.pow(exp)`, returning `None` if ",
+                "overflow occurred or if the result is not a valid `", stringify!($struct), "`."),
+                #[inline]
+                pub fn checked_pow(self, exp: u32) -> Option<Self> {
+                    <$prim>::checked_pow(self.get(), exp).and_then(Self::new)
+                }
+            }
+        }
+
+        impl_binop!([$($gen)*] $struct, $prim, Add, add, AddAssign, add_assign, checked_add, "attempt to add with overflow");
+        impl_binop!([$($gen)*] $struct, $prim, Sub, sub, SubAssign, sub_assign, checked_sub, "attempt to subtract with overflow");
+        impl_binop!([$($gen)*] $struct, $prim, Mul, mul, MulAssign, mul_assign, checked_mul, "attempt to multiply with overflow");
+        impl_binop!([$($gen)*] $struct, $prim, Div, div, DivAssign, div_assign, /, "attempt to divide with overflow");
+        impl_binop!([$($gen)*] $struct, $prim, Rem, rem, RemAssign, rem_assign, %, "attempt to calculate the remainder with overflow");
+    };
+}
+
+macro_rules! impl_binop {
+    ([$($gen:tt)*] $struct:ty, $prim:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $checked:ident, $msg:expr) => {
+        impl_binop!(@impl [$($gen)*] $struct, $prim, $trait, $method, $assign_trait, $assign_method,
+            |lhs: $prim, rhs: $prim| <$prim>::$checked(lhs, rhs), $msg);
+    };
+    // Division and remainder are delegated to the primitive operator, which already panics on a
+    // zero divisor (and on overflow) in all build profiles.
+    ([$($gen:tt)*] $struct:ty, $prim:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $op:tt, $msg:expr) => {
+        impl_binop!(@impl [$($gen)*] $struct, $prim, $trait, $method, $assign_trait, $assign_method,
+            |lhs: $prim, rhs: $prim| Some(lhs $op rhs), $msg);
+    };
+    (@impl [$($gen:tt)*] $struct:ty, $prim:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $f:expr, $msg:expr) => {
+        impl<$($gen)*> core::ops::$trait<$prim> for $struct {
+            type Output = Self;
+
+            #[inline]
+            #[track_caller]
+            fn $method(self, rhs: $prim) -> Self {
+                let f = $f;
+                match f(self.get(), rhs) {
+                    Some(value) => Self::new(value).expect("result equals the excluded value"),
+                    None => panic!($msg),
+                }
+            }
+        }
+
+        impl<$($gen)*> core::ops::$trait for $struct {
+            type Output = Self;
+
+            #[inline]
+            #[track_caller]
+            fn $method(self, rhs: Self) -> Self {
+                <Self as core::ops::$trait<$prim>>::$method(self, rhs.get())
+            }
+        }
+
+        impl<$($gen)*> core::ops::$assign_trait<$prim> for $struct {
+            #[inline]
+            #[track_caller]
+            fn $assign_method(&mut self, rhs: $prim) {
+                *self = <Self as core::ops::$trait<$prim>>::$method(*self, rhs);
+            }
+        }
+
+        impl<$($gen)*> core::ops::$assign_trait for $struct {
+            #[inline]
+            #[track_caller]
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = <Self as core::ops::$trait<$prim>>::$method(*self, rhs.get());
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    macro_rules! test_ops {
+        ($test_name:ident, $struct:ident, $prim:ident, $mask:expr) => {
+            #[test]
+            fn $test_name() {
+                let x = $struct::new(12).unwrap();
+                let y = $struct::new(5).unwrap();
+
+                // checked arithmetic on regular values.
+                assert_eq!(x.checked_add(y).map($struct::get), Some(17));
+                assert_eq!(x.checked_sub(y).map($struct::get), Some(7));
+                assert_eq!(x.checked_mul(y).map($struct::get), Some(60));
+                assert_eq!(x.checked_div(y).map($struct::get), Some(2));
+                assert_eq!(x.checked_rem(y).map($struct::get), Some(2));
+                assert_eq!(y.checked_pow(2).map($struct::get), Some(25));
+
+                // results equal to the mask are rejected.
+                let near = $struct::new(if $mask == <$prim>::MAX {
+                    $mask.wrapping_sub(1)
+                } else {
+                    $mask.wrapping_add(1)
+                })
+                .unwrap();
+                let one = $struct::new(1).unwrap();
+                if $mask == <$prim>::MAX {
+                    assert_eq!(near.checked_add(one), None);
+                } else {
+                    assert_eq!(near.checked_sub(one), None);
+                }
+
+                // operators agree with their checked counterparts.
+                assert_eq!((x + y).get(), 17);
+                assert_eq!((x - 5).get(), 7);
+                assert_eq!((x * y).get(), 60);
+                assert_eq!((x / 5).get(), 2);
+                assert_eq!((x % y).get(), 2);
+
+                let mut z = x;
+                z += 3;
+                z -= y;
+                z *= 2;
+                z /= y;
+                z %= 3;
+                assert_eq!(z.get(), 1);
+            }
+        };
+    }
+
+    test_ops!(test_nonmaxu8, NonMaxU8, u8, u8::MAX);
+    test_ops!(test_nonmaxu16, NonMaxU16, u16, u16::MAX);
+    test_ops!(test_nonmaxu32, NonMaxU32, u32, u32::MAX);
+    test_ops!(test_nonmaxu64, NonMaxU64, u64, u64::MAX);
+    test_ops!(test_nonmaxu128, NonMaxU128, u128, u128::MAX);
+    test_ops!(test_nonmaxusize, NonMaxUsize, usize, usize::MAX);
+
+    test_ops!(test_nonmaxi8, NonMaxI8, i8, i8::MAX);
+    test_ops!(test_nonmaxi16, NonMaxI16, i16, i16::MAX);
+    test_ops!(test_nonmaxi32, NonMaxI32, i32, i32::MAX);
+    test_ops!(test_nonmaxi64, NonMaxI64, i64, i64::MAX);
+    test_ops!(test_nonmaxi128, NonMaxI128, i128, i128::MAX);
+    test_ops!(test_nonmaxisize, NonMaxIsize, isize, isize::MAX);
+
+    test_ops!(test_nonminu8, NonMinU8, u8, u8::MIN);
+    test_ops!(test_nonminu16, NonMinU16, u16, u16::MIN);
+    test_ops!(test_nonminu32, NonMinU32, u32, u32::MIN);
+    test_ops!(test_nonminu64, NonMinU64, u64, u64::MIN);
+    test_ops!(test_nonminu128, NonMinU128, u128, u128::MIN);
+    test_ops!(test_nonminusize, NonMinUsize, usize, usize::MIN);
+
+    test_ops!(test_nonmini8, NonMinI8, i8, i8::MIN);
+    test_ops!(test_nonmini16, NonMinI16, i16, i16::MIN);
+    test_ops!(test_nonmini32, NonMinI32, i32, i32::MIN);
+    test_ops!(test_nonmini64, NonMinI64, i64, i64::MIN);
+    test_ops!(test_nonmini128, NonMinI128, i128, i128::MIN);
+    test_ops!(test_nonminisize, NonMinIsize, isize, isize::MIN);
+
+    #[test]
+    #[should_panic(expected = "result equals the excluded value")]
+    fn test_add_excluded_panics() {
+        let _ = NonMaxU8::new(254).unwrap() + 1;
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to add with overflow")]
+    fn test_add_overflow_panics() {
+        let _ = NonMinU8::new(200).unwrap() + 100;
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn test_sub_overflow_panics() {
+        let _ = NonMaxU8::new(0).unwrap() - 1;
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn test_div_zero_panics() {
+        let _ = NonMaxU8::new(1).unwrap() / 0;
+    }
+
+    #[test]
+    #[should_panic(expected = "result equals the excluded value")]
+    fn test_rem_excluded_panics() {
+        let _ = NonMinU8::new(4).unwrap() % 2;
+    }
+}
@@ -0,0 +1,327 @@
+//! Parsing from strings.
+//!
+//! Parsing reports why it failed through `ParseError`, which distinguishes an invalid digit and an
+//! overflow from a value which is valid for the primitive but equals the excluded value.
+
+use crate::error::{ParseError, ParseErrorKind};
+use crate::{NonExtremeI128, NonExtremeI16, NonExtremeI32, NonExtremeI64, NonExtremeI8};
+use crate::{NonExtremeIsize, NonMaxI128, NonMaxI16, NonMaxI32, NonMaxI64, NonMaxI8, NonMaxIsize};
+use crate::{NonMaxU128, NonMaxU16, NonMaxU32, NonMaxU64, NonMaxU8, NonMaxUsize};
+use crate::{NonMinI128, NonMinI16, NonMinI32, NonMinI64, NonMinI8, NonMinIsize};
+use crate::{NonMinU128, NonMinU16, NonMinU32, NonMinU64, NonMinU8, NonMinUsize};
+use crate::{NonValueI128, NonValueI16, NonValueI32, NonValueI64, NonValueI8, NonValueIsize};
+use crate::{NonValueU128, NonValueU16, NonValueU32, NonValueU64, NonValueU8, NonValueUsize};
+use crate::{RangedI128, RangedI16, RangedI32, RangedI64, RangedI8, RangedIsize};
+use crate::{RangedU128, RangedU16, RangedU32, RangedU64, RangedU8, RangedUsize};
+use core::convert::TryFrom;
+
+/// Parsing of the primitive integers, which reports errors as `ParseError`.
+pub(crate) trait ParsePrim: Sized {
+    /// Parses `src` in the given `radix`, after an optional `+` or `-` sign. If `prefixed` is
+    /// true, the radix is instead taken from an optional `0x`, `0o` or `0b` prefix following the
+    /// sign, in either case, and defaults to ten.
+    fn parse(src: &str, radix: u32, prefixed: bool) -> Result<Self, ParseError>;
+}
+
+macro_rules! impl_parse_prim {
+    ($prim:ident, $signed:expr) => {
+        impl ParsePrim for $prim {
+            fn parse(src: &str, radix: u32, prefixed: bool) -> Result<Self, ParseError> {
+                assert!(
+                    (2..=36).contains(&radix),
+                    "radix must lie in the range `[2, 36]`"
+                );
+
+                let (negative, digits) = match src.as_bytes() {
+                    [] => return Err(ParseError::new(ParseErrorKind::Empty)),
+                    [b'+', rest @ ..] => (false, rest),
+                    [b'-', rest @ ..] if $signed => (true, rest),
+                    rest => (false, rest),
+                };
+
+                let (radix, digits) = match digits {
+                    [b'0', b'x' | b'X', rest @ ..] if prefixed => (16, rest),
+                    [b'0', b'o' | b'O', rest @ ..] if prefixed => (8, rest),
+                    [b'0', b'b' | b'B', rest @ ..] if prefixed => (2, rest),
+                    rest if prefixed => (10, rest),
+                    rest => (radix, rest),
+                };
+
+                if digits.is_empty() {
+                    return Err(ParseError::new(ParseErrorKind::InvalidDigit));
+                }
+
+                // Negative values are accumulated downwards, since `MIN` has no positive
+                // counterpart.
+                let mut result: $prim = 0;
+                for &c in digits {
+                    let digit = match (c as char).to_digit(radix) {
+                        Some(digit) => digit as $prim,
+                        None => return Err(ParseError::new(ParseErrorKind::InvalidDigit)),
+                    };
+
+                    let next = result.checked_mul(radix as $prim);
+                    result = if negative {
+                        next.and_then(|x| x.checked_sub(digit))
+                            .ok_or_else(|| ParseError::new(ParseErrorKind::NegOverflow))?
+                    } else {
+                        next.and_then(|x| x.checked_add(digit))
+                            .ok_or_else(|| ParseError::new(ParseErrorKind::PosOverflow))?
+                    };
+                }
+
+                Ok(result)
+            }
+        }
+    };
+}
+
+impl_parse_prim!(u8, false);
+impl_parse_prim!(u16, false);
+impl_parse_prim!(u32, false);
+impl_parse_prim!(u64, false);
+impl_parse_prim!(u128, false);
+impl_parse_prim!(usize, false);
+
+impl_parse_prim!(i8, true);
+impl_parse_prim!(i16, true);
+impl_parse_prim!(i32, true);
+impl_parse_prim!(i64, true);
+impl_parse_prim!(i128, true);
+impl_parse_prim!(isize, true);
+
+macro_rules! impl_from_str {
+    ($struct:ident, $prim:ident) => {
+        impl_from_str!([] $struct, $prim, "", stringify!($struct));
+    };
+    ($struct:ident, $prim:ident, const $v:ident) => {
+        impl_from_str!(
+            [const $v: $prim] $struct<$v>,
+            $prim,
+            concat!("# const ", stringify!($v), ": ", stringify!($prim), " = 0;\n"),
+            concat!(stringify!($struct), "::<", stringify!($v), ">")
+        );
+    };
+    (ranged $struct:ident, $prim:ident) => {
+        impl_from_str!(
+            [const LO: $prim, const HI: $prim] $struct<LO, HI>,
+            $prim,
+            "",
+            concat!(stringify!($struct), "::<0, 100>")
+        );
+    };
+    // `$setup` is prepended to the doctest, in which the type is written as `$example`.
+    ([$($gen:tt)*] $struct:ty, $prim:ident, $setup:expr, $example:expr) => {
+        impl<$($gen)*> $struct {
+            doc_comment! {
+                concat!("Parses a `", stringify!($struct), "` from a string in the given base, ",
+                "with an optional `+` or `-` sign.\n\n",
+                "# Panics\n",
+                "Panics if `radix` is not in the range from 2 to 36."),
+                #[inline]
+                pub fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseError> {
+                    let value = <$prim as ParsePrim>::parse(src, radix, false)?;
+                    Ok(Self::try_from(value)?)
+                }
+            }
+
+            doc_comment! {
+                concat!("Parses a `", stringify!($struct), "` from a string, where the base is ",
+                "given by an optional `0x`, `0o` or `0b` prefix following the sign. The prefix ",
+                "may also be uppercase. Without prefix, the string is parsed in base ten.\n\n",
+                "```\n",
+                "# use nonminmax::*;\n",
+                $setup,
+                "assert_eq!(", $example, "::from_str_prefixed(\"0x2a\").unwrap().get(), 42);\n",
+                "assert_eq!(", $example, "::from_str_prefixed(\"+0b101010\").unwrap().get(), 42);\n",
+                "assert_eq!(", $example, "::from_str_prefixed(\"42\").unwrap().get(), 42);\n",
+                "```"),
+                #[inline]
+                pub fn from_str_prefixed(src: &str) -> Result<Self, ParseError> {
+                    let value = <$prim as ParsePrim>::parse(src, 10, true)?;
+                    Ok(Self::try_from(value)?)
+                }
+            }
+        }
+
+        impl<$($gen)*> core::str::FromStr for $struct {
+            type Err = ParseError;
+
+            #[inline]
+            fn from_str(src: &str) -> Result<Self, Self::Err> {
+                Self::from_str_radix(src, 10)
+            }
+        }
+    };
+}
+
+impl_from_str!(NonMaxU8, u8);
+impl_from_str!(NonMaxU16, u16);
+impl_from_str!(NonMaxU32, u32);
+impl_from_str!(NonMaxU64, u64);
+impl_from_str!(NonMaxU128, u128);
+impl_from_str!(NonMaxUsize, usize);
+
+impl_from_str!(NonMaxI8, i8);
+impl_from_str!(NonMaxI16, i16);
+impl_from_str!(NonMaxI32, i32);
+impl_from_str!(NonMaxI64, i64);
+impl_from_str!(NonMaxI128, i128);
+impl_from_str!(NonMaxIsize, isize);
+
+impl_from_str!(NonMinU8, u8);
+impl_from_str!(NonMinU16, u16);
+impl_from_str!(NonMinU32, u32);
+impl_from_str!(NonMinU64, u64);
+impl_from_str!(NonMinU128, u128);
+impl_from_str!(NonMinUsize, usize);
+
+impl_from_str!(NonMinI8, i8);
+impl_from_str!(NonMinI16, i16);
+impl_from_str!(NonMinI32, i32);
+impl_from_str!(NonMinI64, i64);
+impl_from_str!(NonMinI128, i128);
+impl_from_str!(NonMinIsize, isize);
+
+impl_from_str!(NonExtremeI8, i8);
+impl_from_str!(NonExtremeI16, i16);
+impl_from_str!(NonExtremeI32, i32);
+impl_from_str!(NonExtremeI64, i64);
+impl_from_str!(NonExtremeI128, i128);
+impl_from_str!(NonExtremeIsize, isize);
+
+impl_from_str!(NonValueU8, u8, const V);
+impl_from_str!(NonValueU16, u16, const V);
+impl_from_str!(NonValueU32, u32, const V);
+impl_from_str!(NonValueU64, u64, const V);
+impl_from_str!(NonValueU128, u128, const V);
+impl_from_str!(NonValueUsize, usize, const V);
+
+impl_from_str!(NonValueI8, i8, const V);
+impl_from_str!(NonValueI16, i16, const V);
+impl_from_str!(NonValueI32, i32, const V);
+impl_from_str!(NonValueI64, i64, const V);
+impl_from_str!(NonValueI128, i128, const V);
+impl_from_str!(NonValueIsize, isize, const V);
+
+impl_from_str!(ranged RangedU8, u8);
+impl_from_str!(ranged RangedU16, u16);
+impl_from_str!(ranged RangedU32, u32);
+impl_from_str!(ranged RangedU64, u64);
+impl_from_str!(ranged RangedU128, u128);
+impl_from_str!(ranged RangedUsize, usize);
+
+impl_from_str!(ranged RangedI8, i8);
+impl_from_str!(ranged RangedI16, i16);
+impl_from_str!(ranged RangedI32, i32);
+impl_from_str!(ranged RangedI64, i64);
+impl_from_str!(ranged RangedI128, i128);
+impl_from_str!(ranged RangedIsize, isize);
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use crate::*;
+    use std::string::ToString;
+
+    fn kind<T: core::str::FromStr<Err = ParseError>>(src: &str) -> ParseErrorKind {
+        *src.parse::<T>().err().unwrap().kind()
+    }
+
+    macro_rules! test_parse {
+        ($test_name:ident, $nonmax:ident, $nonmin:ident, $prim:ident) => {
+            #[test]
+            fn $test_name() {
+                for &value in &[0, 1, 42, <$prim>::MAX - 1, <$prim>::MIN] {
+                    let x: $nonmax = value.to_string().parse().unwrap();
+                    assert_eq!(x.get(), value);
+                    assert_eq!(x.to_string().parse(), Ok(x));
+                }
+
+                for &value in &[1, 42, <$prim>::MAX, <$prim>::MIN + 1] {
+                    let x: $nonmin = value.to_string().parse().unwrap();
+                    assert_eq!(x.get(), value);
+                }
+
+                assert_eq!(
+                    kind::<$nonmax>(&<$prim>::MAX.to_string()),
+                    ParseErrorKind::Max
+                );
+                assert_eq!(
+                    kind::<$nonmin>(&<$prim>::MIN.to_string()),
+                    ParseErrorKind::Min
+                );
+                assert_eq!(kind::<$nonmax>(""), ParseErrorKind::Empty);
+                assert_eq!(kind::<$nonmax>("+"), ParseErrorKind::InvalidDigit);
+                assert_eq!(kind::<$nonmax>("12a"), ParseErrorKind::InvalidDigit);
+                assert_eq!(kind::<$nonmax>("0x12"), ParseErrorKind::InvalidDigit);
+                assert_eq!(
+                    kind::<$nonmax>("1000000000000000000000000000000000000000"),
+                    ParseErrorKind::PosOverflow
+                );
+
+                assert_eq!($nonmax::from_str_radix("2a", 16).unwrap().get(), 42);
+                assert_eq!($nonmax::from_str_radix("+1z", 36).unwrap().get(), 71);
+                assert_eq!($nonmax::from_str_prefixed("0o52").unwrap().get(), 42);
+                assert_eq!($nonmin::from_str_prefixed("0b101010").unwrap().get(), 42);
+                assert_eq!($nonmax::from_str_prefixed("0X2A").unwrap().get(), 42);
+                assert_eq!($nonmax::from_str_prefixed("0O52").unwrap().get(), 42);
+                assert_eq!($nonmin::from_str_prefixed("+0B101010").unwrap().get(), 42);
+                assert_eq!(
+                    $nonmax::from_str_prefixed("0x").unwrap_err().kind(),
+                    &ParseErrorKind::InvalidDigit
+                );
+            }
+        };
+    }
+
+    test_parse!(test_u8, NonMaxU8, NonMinU8, u8);
+    test_parse!(test_u16, NonMaxU16, NonMinU16, u16);
+    test_parse!(test_u32, NonMaxU32, NonMinU32, u32);
+    test_parse!(test_u64, NonMaxU64, NonMinU64, u64);
+    test_parse!(test_u128, NonMaxU128, NonMinU128, u128);
+    test_parse!(test_usize, NonMaxUsize, NonMinUsize, usize);
+
+    test_parse!(test_i8, NonMaxI8, NonMinI8, i8);
+    test_parse!(test_i16, NonMaxI16, NonMinI16, i16);
+    test_parse!(test_i32, NonMaxI32, NonMinI32, i32);
+    test_parse!(test_i64, NonMaxI64, NonMinI64, i64);
+    test_parse!(test_i128, NonMaxI128, NonMinI128, i128);
+    test_parse!(test_isize, NonMaxIsize, NonMinIsize, isize);
+
+    #[test]
+    fn test_sign() {
+        assert_eq!(kind::<NonMaxU8>("-1"), ParseErrorKind::InvalidDigit);
+        assert_eq!(kind::<NonMaxU8>("-0"), ParseErrorKind::InvalidDigit);
+        assert_eq!(kind::<NonMaxI8>("-129"), ParseErrorKind::NegOverflow);
+        assert_eq!(kind::<NonExtremeI8>("-128"), ParseErrorKind::Min);
+        assert_eq!(kind::<NonExtremeI8>("127"), ParseErrorKind::Max);
+        assert_eq!("-127".parse::<NonExtremeI8>().unwrap().get(), -127);
+        assert_eq!(kind::<NonMaxI8>("-"), ParseErrorKind::InvalidDigit);
+        assert_eq!(NonMaxI8::from_str_prefixed("-0x80").unwrap().get(), -128);
+        assert_eq!(NonMaxI8::from_str_prefixed("-0X80").unwrap().get(), -128);
+        assert_eq!(NonMinI8::from_str_radix("-7f", 16).unwrap().get(), -127);
+        assert_eq!(
+            NonMinI8::from_str_prefixed("-0x80").unwrap_err().kind(),
+            &ParseErrorKind::Min
+        );
+    }
+
+    #[test]
+    fn test_nonvalue() {
+        let x: NonValueU32<0xDEAD> = "48879".parse().unwrap();
+        assert_eq!(x.get(), 0xBEEF);
+        let x = NonValueU32::<0xDEAD>::from_str_prefixed("0xbeef").unwrap();
+        assert_eq!(x.get(), 0xBEEF);
+        assert_eq!(kind::<NonValueU32<0xDEAD>>("57005"), ParseErrorKind::Value);
+        assert_eq!(kind::<NonValueI8<-1>>("-1"), ParseErrorKind::Value);
+        assert_eq!(kind::<NonValueI8<-1>>("128"), ParseErrorKind::PosOverflow);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_radix() {
+        let _ = NonMaxU8::from_str_radix("1", 37);
+    }
+}
@@ -0,0 +1,427 @@
+//! Integer types which are known to lie in the range `LO..=HI`, given as const generic parameters.
+//!
+//! Like the other types, these types store `value ^ mask` in the `NonZeroX` type of the same width.
+//! The mask is any value outside the range: `MIN` if `LO > MIN`, and otherwise `MAX`. Hence, the
+//! range cannot include every value of the primitive, which is checked at compile time.
+//!
+//! Arithmetic either returns an `Option` of the same type (`checked_add`, ...), like the other
+//! types, or returns a type with a wider range (`widening_add`, ...). For the latter, the range of
+//! the result is given by the caller and is checked at compile time to contain every possible
+//! result, since stable Rust cannot compute it in the type.
+
+use crate::error::{NonMinMaxError, NonMinMaxErrorKind};
+use core::convert::TryFrom;
+use core::fmt;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+
+macro_rules! impl_ranged {
+    ($struct:ident, $nonzero:ident, $prim:ident) => {
+        doc_comment! {
+            concat!("An integer of type `", stringify!($prim), "` which is known to lie in the range `LO..=HI`.
+
+The range cannot include every value of `", stringify!($prim), "`, which leaves a niche for
+the niche filling optimization, meaning that `Option<", stringify!($struct), "<LO, HI>>`
+takes up the same amount of space as `", stringify!($prim), "`.
+
+```
+# use nonminmax::*;
+type Percent = ", stringify!($struct), "<0, 100>;
+
+let x = Percent::new(42).unwrap();
+assert_eq!(x.get(), 42);
+assert_eq!(Percent::new(101), None);
+assert_eq!(Percent::clamp_new(120).get(), 100);
+
+let y: ", stringify!($struct), "<0, 127> = x.widen();
+assert_eq!(y.get(), 42);
+
+use std::mem::size_of;
+assert_eq!(size_of::<Option<Percent>>(), size_of::<", stringify!($prim), ">());
+```"),
+            #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+            #[repr(transparent)]
+            pub struct $struct<const LO: $prim, const HI: $prim> {
+                value: $nonzero,
+            }
+        }
+
+        impl<const LO: $prim, const HI: $prim> $struct<LO, HI> {
+            /// Ensures that the range is non-empty and leaves a niche.
+            const VALID: () = assert!(
+                LO <= HI && (LO != <$prim>::MIN || HI != <$prim>::MAX),
+                "the range must be non-empty and cannot include every value"
+            );
+
+            /// A value outside of the range, which is used as the mask.
+            const MASK: $prim = if LO != <$prim>::MIN { <$prim>::MIN } else { <$prim>::MAX };
+
+            /// The smallest value that can be represented, which is `LO`.
+            pub const MIN: Self = unsafe { Self::new_unchecked(LO) };
+
+            /// The largest value that can be represented, which is `HI`.
+            pub const MAX: Self = unsafe { Self::new_unchecked(HI) };
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($struct), "` by checking if the value lies in `LO..=HI`."),
+                #[inline(always)]
+                pub const fn new(value: $prim) -> Option<Self> {
+                    if LO <= value && value <= HI {
+                        unsafe { Some(Self::new_unchecked(value)) }
+                    } else {
+                        None
+                    }
+                }
+            }
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($struct), "`, or panics if the value does not lie in `LO..=HI`.\n",
+                " # Panics\n",
+                "Panics if the value does not lie in `LO..=HI`. In a `const` context, this results ",
+                "in a compile-time error."),
+                #[inline]
+                #[track_caller]
+                pub const fn new_or_panic(value: $prim) -> Self {
+                    match Self::new(value) {
+                        Some(value) => value,
+                        None => panic!("value does not lie in `LO..=HI`"),
+                    }
+                }
+            }
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($struct), "` without checking if the value lies in `LO..=HI`.\n",
+                " # Safety\n",
+                "The value must lie in `LO..=HI`."),
+                #[inline(always)]
+                pub const unsafe fn new_unchecked(value: $prim) -> Self {
+                    #[allow(clippy::let_unit_value)]
+                    let () = Self::VALID;
+                    let value = $nonzero::new_unchecked(value ^ Self::MASK);
+
+                    Self { value }
+                }
+            }
+
+            /// Returns the integer value.
+            #[inline(always)]
+            pub const fn get(self) -> $prim {
+                self.value.get() ^ Self::MASK
+            }
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($struct), "` by clamping `value` to ",
+                "the range `LO..=HI`."),
+                #[inline]
+                pub const fn clamp_new(value: $prim) -> Self {
+                    let value = if value < LO {
+                        LO
+                    } else if value > HI {
+                        HI
+                    } else {
+                        value
+                    };
+
+                    unsafe { Self::new_unchecked(value) }
+                }
+            }
+
+            /// Checked integer addition. Computes `self + rhs`, returning `None` if the result
+            /// does not lie in `LO..=HI`.
+            #[inline]
+            pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+                match self.get().checked_add(rhs.get()) {
+                    Some(value) => Self::new(value),
+                    None => None,
+                }
+            }
+
+            /// Checked integer subtraction. Computes `self - rhs`, returning `None` if the result
+            /// does not lie in `LO..=HI`.
+            #[inline]
+            pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+                match self.get().checked_sub(rhs.get()) {
+                    Some(value) => Self::new(value),
+                    None => None,
+                }
+            }
+
+            /// Checked integer multiplication. Computes `self * rhs`, returning `None` if the
+            /// result does not lie in `LO..=HI`.
+            #[inline]
+            pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+                match self.get().checked_mul(rhs.get()) {
+                    Some(value) => Self::new(value),
+                    None => None,
+                }
+            }
+
+            doc_comment! {
+                concat!("Converts `self` into a `", stringify!($struct), "` with a range which ",
+                "contains `LO..=HI`. This is checked at compile time, so this can never fail."),
+                #[inline]
+                pub const fn widen<const LO2: $prim, const HI2: $prim>(self) -> $struct<LO2, HI2> {
+                    const {
+                        assert!(LO2 <= LO && HI <= HI2, "the target range must contain `LO..=HI`");
+                    }
+
+                    unsafe { $struct::new_unchecked(self.get()) }
+                }
+            }
+
+            doc_comment! {
+                concat!("Converts `self` into a `", stringify!($struct), "` with a different ",
+                "range, returning `None` if the value does not lie in that range."),
+                #[inline]
+                pub const fn narrow<const LO2: $prim, const HI2: $prim>(self) -> Option<$struct<LO2, HI2>> {
+                    $struct::new(self.get())
+                }
+            }
+
+            doc_comment! {
+                concat!("Computes `self + rhs` as a `", stringify!($struct), "<LO3, HI3>`. The ",
+                "range `LO3..=HI3` must contain every possible sum, which is checked at compile ",
+                "time, so this can never fail."),
+                #[inline]
+                pub const fn widening_add<const LO2: $prim, const HI2: $prim, const LO3: $prim, const HI3: $prim>(
+                    self,
+                    rhs: $struct<LO2, HI2>,
+                ) -> $struct<LO3, HI3> {
+                    const {
+                        assert!(
+                            matches!(LO.checked_add(LO2), Some(lo) if LO3 <= lo)
+                                && matches!(HI.checked_add(HI2), Some(hi) if hi <= HI3),
+                            "the target range must contain every possible sum"
+                        );
+                    }
+
+                    unsafe { $struct::new_unchecked(self.get() + rhs.get()) }
+                }
+            }
+
+            doc_comment! {
+                concat!("Computes `self - rhs` as a `", stringify!($struct), "<LO3, HI3>`. The ",
+                "range `LO3..=HI3` must contain every possible difference, which is checked at ",
+                "compile time, so this can never fail."),
+                #[inline]
+                pub const fn widening_sub<const LO2: $prim, const HI2: $prim, const LO3: $prim, const HI3: $prim>(
+                    self,
+                    rhs: $struct<LO2, HI2>,
+                ) -> $struct<LO3, HI3> {
+                    const {
+                        assert!(
+                            matches!(LO.checked_sub(HI2), Some(lo) if LO3 <= lo)
+                                && matches!(HI.checked_sub(LO2), Some(hi) if hi <= HI3),
+                            "the target range must contain every possible difference"
+                        );
+                    }
+
+                    unsafe { $struct::new_unchecked(self.get() - rhs.get()) }
+                }
+            }
+
+            doc_comment! {
+                concat!("Computes `self * rhs` as a `", stringify!($struct), "<LO3, HI3>`. The ",
+                "range `LO3..=HI3` must contain every possible product, which is checked at ",
+                "compile time, so this can never fail."),
+                #[inline]
+                pub const fn widening_mul<const LO2: $prim, const HI2: $prim, const LO3: $prim, const HI3: $prim>(
+                    self,
+                    rhs: $struct<LO2, HI2>,
+                ) -> $struct<LO3, HI3> {
+                    // The extreme products are found at the corners of the two ranges.
+                    const {
+                        let corners = [
+                            LO.checked_mul(LO2),
+                            LO.checked_mul(HI2),
+                            HI.checked_mul(LO2),
+                            HI.checked_mul(HI2),
+                        ];
+
+                        let mut i = 0;
+                        while i < corners.len() {
+                            assert!(
+                                matches!(corners[i], Some(x) if LO3 <= x && x <= HI3),
+                                "the target range must contain every possible product"
+                            );
+                            i += 1;
+                        }
+                    }
+
+                    unsafe { $struct::new_unchecked(self.get() * rhs.get()) }
+                }
+            }
+        }
+
+        impl<const LO: $prim, const HI: $prim> From<$struct<LO, HI>> for $prim {
+            fn from(nontype: $struct<LO, HI>) -> Self {
+                nontype.get()
+            }
+        }
+
+        impl<const LO: $prim, const HI: $prim> TryFrom<$prim> for $struct<LO, HI> {
+            type Error = NonMinMaxError;
+
+            fn try_from(value: $prim) -> Result<Self, Self::Error> {
+                Self::new(value).ok_or_else(|| {
+                    NonMinMaxError::new(if value < LO {
+                        NonMinMaxErrorKind::NegOverflow
+                    } else {
+                        NonMinMaxErrorKind::PosOverflow
+                    })
+                })
+            }
+        }
+
+        impl<const LO: $prim, const HI: $prim> PartialOrd for $struct<LO, HI> {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<const LO: $prim, const HI: $prim> Ord for $struct<LO, HI> {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.get().cmp(&other.get())
+            }
+        }
+
+        impl<const LO: $prim, const HI: $prim> fmt::Debug for $struct<LO, HI> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($struct)).field(&self.get()).finish()
+            }
+        }
+
+        impl_fmt!(
+            [const LO: $prim, const HI: $prim] $struct<LO, HI>,
+            Display, Binary, Octal, LowerHex, UpperHex, LowerExp, UpperExp
+        );
+    };
+}
+
+impl_ranged!(RangedU8, NonZeroU8, u8);
+impl_ranged!(RangedU16, NonZeroU16, u16);
+impl_ranged!(RangedU32, NonZeroU32, u32);
+impl_ranged!(RangedU64, NonZeroU64, u64);
+impl_ranged!(RangedU128, NonZeroU128, u128);
+impl_ranged!(RangedUsize, NonZeroUsize, usize);
+
+impl_ranged!(RangedI8, NonZeroI8, i8);
+impl_ranged!(RangedI16, NonZeroI16, i16);
+impl_ranged!(RangedI32, NonZeroI32, i32);
+impl_ranged!(RangedI64, NonZeroI64, i64);
+impl_ranged!(RangedI128, NonZeroI128, i128);
+impl_ranged!(RangedIsize, NonZeroIsize, isize);
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use crate::*;
+    use core::convert::TryFrom;
+    use core::mem::size_of;
+    use std::format;
+
+    macro_rules! test_ranged {
+        ($test_name:ident, $struct:ident, $prim:ident) => {
+            #[test]
+            fn $test_name() {
+                type Small = $struct<10, 20>;
+                type Low = $struct<{ <$prim>::MIN }, 20>;
+                type High = $struct<10, { <$prim>::MAX }>;
+
+                assert_eq!(size_of::<Option<Small>>(), size_of::<$prim>());
+                assert_eq!(size_of::<Option<Low>>(), size_of::<$prim>());
+                assert_eq!(size_of::<Option<High>>(), size_of::<$prim>());
+
+                assert_eq!(Small::new(9), None);
+                assert_eq!(Small::new(21), None);
+                assert_eq!(Small::new(10).unwrap().get(), 10);
+                assert_eq!(Small::new(20).unwrap().get(), 20);
+                assert_eq!(Low::new(<$prim>::MIN).unwrap().get(), <$prim>::MIN);
+                assert_eq!(High::new(<$prim>::MAX).unwrap().get(), <$prim>::MAX);
+                assert_eq!(Small::MIN.get(), 10);
+                assert_eq!(Small::MAX.get(), 20);
+                assert_eq!(Small::clamp_new(0).get(), 10);
+                assert_eq!(Small::clamp_new(<$prim>::MAX).get(), 20);
+
+                let err = Small::try_from(5).unwrap_err();
+                assert_eq!(err.kind(), &NonMinMaxErrorKind::NegOverflow);
+                let err = Small::try_from(25).unwrap_err();
+                assert_eq!(err.kind(), &NonMinMaxErrorKind::PosOverflow);
+                assert_eq!(<$prim>::from(Small::try_from(15).unwrap()), 15);
+
+                let x = Small::new(15).unwrap();
+                let y = $struct::<0, 10>::new(5).unwrap();
+                assert_eq!(y.checked_add(y).unwrap().get(), 10);
+                assert_eq!(y.checked_add($struct::new(6).unwrap()), None);
+                assert_eq!(x.checked_add(x), None);
+                assert_eq!(High::MAX.checked_add(High::MIN), None);
+                assert_eq!(x.checked_sub(Small::MIN), None);
+                assert_eq!(Small::MAX.checked_sub(Small::MIN).unwrap().get(), 10);
+                assert_eq!(y.checked_mul($struct::new(2).unwrap()).unwrap().get(), 10);
+                assert_eq!(y.checked_mul(y), None);
+
+                let y: $struct<20, 40> = x.widening_add(Small::MAX);
+                assert_eq!(y.get(), 35);
+                let y: $struct<0, 20> = x.widening_sub($struct::<0, 10>::new(10).unwrap());
+                assert_eq!(y.get(), 5);
+                let y: $struct<10, 100> = x.widening_mul($struct::<1, 5>::new(3).unwrap());
+                assert_eq!(y.get(), 45);
+
+                let y: $struct<0, 100> = x.widen();
+                assert_eq!(y.get(), 15);
+                assert_eq!(y.narrow::<10, 20>(), Some(x));
+                assert_eq!(y.narrow::<16, 20>(), None);
+
+                assert!(Small::MIN < x && x < Small::MAX);
+                assert_eq!(format!("{:?}", x), concat!(stringify!($struct), "(15)"));
+                assert_eq!(format!("{}", x), "15");
+                assert_eq!(format!("{:#x} {:b} {:e}", x, x, x), "0xf 1111 1.5e1");
+
+                assert_eq!("15".parse::<Small>(), Ok(x));
+                assert_eq!(Small::from_str_radix("f", 16), Ok(x));
+                assert_eq!(Small::from_str_prefixed("0b1111"), Ok(x));
+                assert_eq!(
+                    "21".parse::<Small>().unwrap_err().kind(),
+                    &ParseErrorKind::PosOverflow
+                );
+                assert_eq!(
+                    "9".parse::<Small>().unwrap_err().kind(),
+                    &ParseErrorKind::NegOverflow
+                );
+            }
+        };
+    }
+
+    test_ranged!(test_u8, RangedU8, u8);
+    test_ranged!(test_u16, RangedU16, u16);
+    test_ranged!(test_u32, RangedU32, u32);
+    test_ranged!(test_u64, RangedU64, u64);
+    test_ranged!(test_u128, RangedU128, u128);
+    test_ranged!(test_usize, RangedUsize, usize);
+
+    test_ranged!(test_i8, RangedI8, i8);
+    test_ranged!(test_i16, RangedI16, i16);
+    test_ranged!(test_i32, RangedI32, i32);
+    test_ranged!(test_i64, RangedI64, i64);
+    test_ranged!(test_i128, RangedI128, i128);
+    test_ranged!(test_isize, RangedIsize, isize);
+
+    #[test]
+    fn test_signed() {
+        let x = RangedI32::<-10, 10>::new(-7).unwrap();
+        let y = RangedI32::<-3, 5>::new(4).unwrap();
+
+        let sum: RangedI32<-13, 15> = x.widening_add(y);
+        assert_eq!(sum.get(), -3);
+        let diff: RangedI32<-15, 13> = x.widening_sub(y);
+        assert_eq!(diff.get(), -11);
+        let product: RangedI32<-50, 50> = x.widening_mul(y);
+        assert_eq!(product.get(), -28);
+
+        assert_eq!(size_of::<Option<RangedI8<-128, 0>>>(), 1);
+        assert_eq!(size_of::<Option<RangedI8<0, 127>>>(), 1);
+    }
+}
@@ -0,0 +1,374 @@
+//! Integer types which exclude their `K` largest values, which requires the `nightly` feature.
+//!
+//! `K` is not a parameter of the types: only `K = 2`, `3` and `4` exist, as the `NonMax2X`,
+//! `NonMax3X` and `NonMax4X` types for every primitive `X`. A single excluded value is covered by
+//! `NonMaxX`.
+//!
+//! The value is stored unchanged in a pattern type which declares the valid range
+//! `MIN..=MAX - K`, so each of the `K` reserved values is a separate niche. Hence, nested `Option`s
+//! and small enums such as "missing / tombstone / value" take up the same amount of space as the
+//! primitive. The reserved values are numbered as slots from `MAX - K + 1` upwards, which allows
+//! code that inspects the encoded words to tell them apart.
+
+use crate::error::{NonMinMaxError, NonMinMaxErrorKind};
+use crate::{NonMaxI128, NonMaxI16, NonMaxI32, NonMaxI64, NonMaxI8, NonMaxIsize};
+use crate::{NonMaxU128, NonMaxU16, NonMaxU32, NonMaxU64, NonMaxU8, NonMaxUsize};
+use core::convert::TryFrom;
+use core::fmt;
+
+/// The pattern type which excludes the `K` largest values, and the upper bound of its range.
+trait Reserved<const K: u32> {
+    type Repr: Copy;
+    const HIGHEST: Self;
+}
+
+macro_rules! impl_reserved {
+    ($struct:ident, $nonmax:ident, $prim:ident, $k:literal) => {
+        impl Reserved<$k> for $prim {
+            type Repr = core::pattern_type!(
+                $prim is <$prim as crate::NicheInt>::MIN..=<$prim as Reserved<$k>>::HIGHEST
+            );
+            const HIGHEST: Self = <$prim>::MAX - $k;
+        }
+
+        doc_comment! {
+            concat!("An integer of type `", stringify!($prim), "` which is known to not equal any of its ",
+            stringify!($k), " largest values.
+
+Each excluded value is a separate niche, so up to ", stringify!($k), " nested `Option`s, or an
+enum with ", stringify!($k), " variants besides the value, take up the same amount of space as
+`", stringify!($prim), "`. This type requires the `nightly` feature.
+
+```
+# use nonminmax::*;
+enum Slot {
+    Missing,
+    Tombstone,
+    Value(", stringify!($struct), "),
+}
+
+let x = ", stringify!($struct), "::new(42).unwrap();
+assert_eq!(x.get(), 42);
+assert_eq!(", stringify!($struct), "::new(", stringify!($prim), "::MAX), None);
+assert_eq!(", stringify!($struct), "::reserved_slot(", stringify!($prim), "::MAX), Some(", stringify!($k), " - 1));
+
+use std::mem::size_of;
+assert_eq!(size_of::<Option<Option<", stringify!($struct), ">>>(), size_of::<", stringify!($prim), ">());
+assert_eq!(size_of::<Slot>(), size_of::<", stringify!($prim), ">());
+```"),
+            #[derive(Clone, Copy)]
+            #[repr(transparent)]
+            pub struct $struct {
+                value: <$prim as Reserved<$k>>::Repr,
+            }
+        }
+
+        impl $struct {
+            /// The number of reserved values.
+            pub const SLOTS: u32 = $k;
+
+            doc_comment! {
+                concat!("The smallest value that can be represented by `", stringify!($struct), "`."),
+                pub const MIN: Self = unsafe { Self::new_unchecked(<$prim>::MIN) };
+            }
+
+            doc_comment! {
+                concat!("The largest value that can be represented by `", stringify!($struct), "`."),
+                pub const MAX: Self = unsafe { Self::new_unchecked(<$prim as Reserved<$k>>::HIGHEST) };
+            }
+
+            /// The size of this type in bits.
+            pub const BITS: u32 = <$prim>::BITS;
+
+            /// The value `0`.
+            pub const ZERO: Self = unsafe { Self::new_unchecked(0) };
+
+            /// The value `1`.
+            pub const ONE: Self = unsafe { Self::new_unchecked(1) };
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($struct), "` by checking if the value is not reserved."),
+                #[inline(always)]
+                pub const fn new(value: $prim) -> Option<Self> {
+                    if value <= <$prim as Reserved<$k>>::HIGHEST {
+                        unsafe { Some(Self::new_unchecked(value)) }
+                    } else {
+                        None
+                    }
+                }
+            }
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($struct), "`, or panics if the value is reserved.\n",
+                " # Panics\n",
+                "Panics if the value is one of the ", stringify!($k), " largest values. In a `const` ",
+                "context, this results in a compile-time error."),
+                #[inline]
+                #[track_caller]
+                pub const fn new_or_panic(value: $prim) -> Self {
+                    match Self::new(value) {
+                        Some(value) => value,
+                        None => panic!("value is reserved"),
+                    }
+                }
+            }
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($struct), "` without checking if the value is reserved.\n",
+                " # Safety\n",
+                "The value cannot be one of the ", stringify!($k), " largest values."),
+                #[inline(always)]
+                pub const unsafe fn new_unchecked(value: $prim) -> Self {
+                    let value = core::mem::transmute::<$prim, <$prim as Reserved<$k>>::Repr>(value);
+
+                    Self { value }
+                }
+            }
+
+            /// Returns the integer value.
+            #[inline(always)]
+            pub const fn get(self) -> $prim {
+                unsafe { core::mem::transmute::<<$prim as Reserved<$k>>::Repr, $prim>(self.value) }
+            }
+
+            doc_comment! {
+                concat!("Returns the slot of an encoded `word`, or `None` if it encodes a value. ",
+                "The slots are numbered from `", stringify!($prim), "::MAX - ", stringify!($k),
+                " + 1` upwards, so the slot of `", stringify!($prim), "::MAX` is `", stringify!($k), " - 1`."),
+                #[inline]
+                pub const fn reserved_slot(word: $prim) -> Option<u32> {
+                    if word > <$prim as Reserved<$k>>::HIGHEST {
+                        Some((word - <$prim as Reserved<$k>>::HIGHEST - 1) as u32)
+                    } else {
+                        None
+                    }
+                }
+            }
+
+            /// Returns the encoded word of a reserved `slot`, or `None` if the slot does not exist.
+            /// This is the inverse of `reserved_slot`.
+            #[inline]
+            pub const fn reserved_word(slot: u32) -> Option<$prim> {
+                if slot < $k {
+                    Some(<$prim as Reserved<$k>>::HIGHEST + 1 + slot as $prim)
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl From<$struct> for $prim {
+            fn from(value: $struct) -> Self {
+                value.get()
+            }
+        }
+
+        impl TryFrom<$prim> for $struct {
+            type Error = NonMinMaxError;
+
+            fn try_from(value: $prim) -> Result<Self, Self::Error> {
+                Self::new(value).ok_or_else(|| NonMinMaxError::new(NonMinMaxErrorKind::Reserved))
+            }
+        }
+
+        impl From<$struct> for $nonmax {
+            fn from(value: $struct) -> Self {
+                unsafe { Self::new_unchecked(value.get()) }
+            }
+        }
+
+        impl TryFrom<$nonmax> for $struct {
+            type Error = NonMinMaxError;
+
+            fn try_from(value: $nonmax) -> Result<Self, Self::Error> {
+                Self::try_from(value.get())
+            }
+        }
+
+        // Pattern types do not implement these traits, so these compare the values instead.
+        impl PartialEq for $struct {
+            fn eq(&self, other: &Self) -> bool {
+                self.get() == other.get()
+            }
+        }
+
+        impl Eq for $struct {}
+
+        impl core::hash::Hash for $struct {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.get().hash(state);
+            }
+        }
+
+        impl PartialOrd for $struct {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $struct {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.get().cmp(&other.get())
+            }
+        }
+
+        impl fmt::Debug for $struct {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($struct)).field(&self.get()).finish()
+            }
+        }
+
+        impl_fmt!($struct, Display, Binary, Octal, LowerHex, UpperHex, LowerExp, UpperExp);
+    };
+}
+
+impl_reserved!(NonMax2U8, NonMaxU8, u8, 2);
+impl_reserved!(NonMax2U16, NonMaxU16, u16, 2);
+impl_reserved!(NonMax2U32, NonMaxU32, u32, 2);
+impl_reserved!(NonMax2U64, NonMaxU64, u64, 2);
+impl_reserved!(NonMax2U128, NonMaxU128, u128, 2);
+impl_reserved!(NonMax2Usize, NonMaxUsize, usize, 2);
+
+impl_reserved!(NonMax2I8, NonMaxI8, i8, 2);
+impl_reserved!(NonMax2I16, NonMaxI16, i16, 2);
+impl_reserved!(NonMax2I32, NonMaxI32, i32, 2);
+impl_reserved!(NonMax2I64, NonMaxI64, i64, 2);
+impl_reserved!(NonMax2I128, NonMaxI128, i128, 2);
+impl_reserved!(NonMax2Isize, NonMaxIsize, isize, 2);
+
+impl_reserved!(NonMax3U8, NonMaxU8, u8, 3);
+impl_reserved!(NonMax3U16, NonMaxU16, u16, 3);
+impl_reserved!(NonMax3U32, NonMaxU32, u32, 3);
+impl_reserved!(NonMax3U64, NonMaxU64, u64, 3);
+impl_reserved!(NonMax3U128, NonMaxU128, u128, 3);
+impl_reserved!(NonMax3Usize, NonMaxUsize, usize, 3);
+
+impl_reserved!(NonMax3I8, NonMaxI8, i8, 3);
+impl_reserved!(NonMax3I16, NonMaxI16, i16, 3);
+impl_reserved!(NonMax3I32, NonMaxI32, i32, 3);
+impl_reserved!(NonMax3I64, NonMaxI64, i64, 3);
+impl_reserved!(NonMax3I128, NonMaxI128, i128, 3);
+impl_reserved!(NonMax3Isize, NonMaxIsize, isize, 3);
+
+impl_reserved!(NonMax4U8, NonMaxU8, u8, 4);
+impl_reserved!(NonMax4U16, NonMaxU16, u16, 4);
+impl_reserved!(NonMax4U32, NonMaxU32, u32, 4);
+impl_reserved!(NonMax4U64, NonMaxU64, u64, 4);
+impl_reserved!(NonMax4U128, NonMaxU128, u128, 4);
+impl_reserved!(NonMax4Usize, NonMaxUsize, usize, 4);
+
+impl_reserved!(NonMax4I8, NonMaxI8, i8, 4);
+impl_reserved!(NonMax4I16, NonMaxI16, i16, 4);
+impl_reserved!(NonMax4I32, NonMaxI32, i32, 4);
+impl_reserved!(NonMax4I64, NonMaxI64, i64, 4);
+impl_reserved!(NonMax4I128, NonMaxI128, i128, 4);
+impl_reserved!(NonMax4Isize, NonMaxIsize, isize, 4);
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use crate::*;
+    use core::convert::TryFrom;
+    use core::mem::{size_of, transmute};
+    use std::format;
+
+    macro_rules! test_reserved {
+        ($test_name:ident, $struct:ident, $nonmax:ident, $prim:ident, $k:literal) => {
+            #[test]
+            fn $test_name() {
+                let x = $struct::new(100).unwrap();
+                assert_eq!(x.get(), 100);
+                assert_eq!(<$prim>::from(x), 100);
+                assert_eq!($struct::try_from(100), Ok(x));
+                assert_eq!($nonmax::from(x).get(), 100);
+                assert_eq!($struct::try_from($nonmax::new(100).unwrap()), Ok(x));
+
+                // the `K` largest values are reserved.
+                assert_eq!($struct::MAX.get(), <$prim>::MAX - $k);
+                assert_eq!($struct::MIN.get(), <$prim>::MIN);
+                assert_eq!($struct::new(<$prim>::MAX - $k), Some($struct::MAX));
+                for slot in 0..$k {
+                    let word = <$prim>::MAX - $k + 1 + slot as $prim;
+                    assert_eq!($struct::new(word), None);
+                    assert_eq!(
+                        $struct::try_from(word).unwrap_err().kind(),
+                        &NonMinMaxErrorKind::Reserved
+                    );
+                    assert_eq!($struct::reserved_slot(word), Some(slot));
+                    assert_eq!($struct::reserved_word(slot), Some(word));
+                }
+                assert_eq!($struct::reserved_slot(<$prim>::MAX - $k), None);
+                assert_eq!($struct::reserved_slot(0), None);
+                assert_eq!($struct::reserved_word($k), None);
+                assert_eq!($struct::SLOTS, $k);
+
+                // the value is stored unchanged, and each reserved value is a niche.
+                assert_eq!(unsafe { transmute::<$struct, $prim>(x) }, 100);
+                assert_eq!(size_of::<Option<$struct>>(), size_of::<$prim>());
+                assert_eq!(size_of::<Option<Option<$struct>>>(), size_of::<$prim>());
+
+                assert!($struct::MIN < x && x < $struct::MAX);
+                assert_eq!(format!("{:?}", x), concat!(stringify!($struct), "(100)"));
+                assert_eq!(format!("{:x}", x), "64");
+            }
+        };
+    }
+
+    test_reserved!(test_2u8, NonMax2U8, NonMaxU8, u8, 2);
+    test_reserved!(test_2u16, NonMax2U16, NonMaxU16, u16, 2);
+    test_reserved!(test_2u32, NonMax2U32, NonMaxU32, u32, 2);
+    test_reserved!(test_2u64, NonMax2U64, NonMaxU64, u64, 2);
+    test_reserved!(test_2u128, NonMax2U128, NonMaxU128, u128, 2);
+    test_reserved!(test_2usize, NonMax2Usize, NonMaxUsize, usize, 2);
+
+    test_reserved!(test_2i8, NonMax2I8, NonMaxI8, i8, 2);
+    test_reserved!(test_2i16, NonMax2I16, NonMaxI16, i16, 2);
+    test_reserved!(test_2i32, NonMax2I32, NonMaxI32, i32, 2);
+    test_reserved!(test_2i64, NonMax2I64, NonMaxI64, i64, 2);
+    test_reserved!(test_2i128, NonMax2I128, NonMaxI128, i128, 2);
+    test_reserved!(test_2isize, NonMax2Isize, NonMaxIsize, isize, 2);
+
+    test_reserved!(test_3u8, NonMax3U8, NonMaxU8, u8, 3);
+    test_reserved!(test_3u16, NonMax3U16, NonMaxU16, u16, 3);
+    test_reserved!(test_3u32, NonMax3U32, NonMaxU32, u32, 3);
+    test_reserved!(test_3u64, NonMax3U64, NonMaxU64, u64, 3);
+    test_reserved!(test_3u128, NonMax3U128, NonMaxU128, u128, 3);
+    test_reserved!(test_3usize, NonMax3Usize, NonMaxUsize, usize, 3);
+
+    test_reserved!(test_3i8, NonMax3I8, NonMaxI8, i8, 3);
+    test_reserved!(test_3i16, NonMax3I16, NonMaxI16, i16, 3);
+    test_reserved!(test_3i32, NonMax3I32, NonMaxI32, i32, 3);
+    test_reserved!(test_3i64, NonMax3I64, NonMaxI64, i64, 3);
+    test_reserved!(test_3i128, NonMax3I128, NonMaxI128, i128, 3);
+    test_reserved!(test_3isize, NonMax3Isize, NonMaxIsize, isize, 3);
+
+    test_reserved!(test_4u8, NonMax4U8, NonMaxU8, u8, 4);
+    test_reserved!(test_4u16, NonMax4U16, NonMaxU16, u16, 4);
+    test_reserved!(test_4u32, NonMax4U32, NonMaxU32, u32, 4);
+    test_reserved!(test_4u64, NonMax4U64, NonMaxU64, u64, 4);
+    test_reserved!(test_4u128, NonMax4U128, NonMaxU128, u128, 4);
+    test_reserved!(test_4usize, NonMax4Usize, NonMaxUsize, usize, 4);
+
+    test_reserved!(test_4i8, NonMax4I8, NonMaxI8, i8, 4);
+    test_reserved!(test_4i16, NonMax4I16, NonMaxI16, i16, 4);
+    test_reserved!(test_4i32, NonMax4I32, NonMaxI32, i32, 4);
+    test_reserved!(test_4i64, NonMax4I64, NonMaxI64, i64, 4);
+    test_reserved!(test_4i128, NonMax4I128, NonMaxI128, i128, 4);
+    test_reserved!(test_4isize, NonMax4Isize, NonMaxIsize, isize, 4);
+
+    #[test]
+    fn test_nested() {
+        type T = NonMax4U32;
+        assert_eq!(size_of::<Option<Option<Option<Option<T>>>>>(), 4);
+        assert!(size_of::<Option<Option<Option<Option<Option<T>>>>>>() > 4);
+
+        // the order in which the compiler assigns the niches is unspecified, but both words are
+        // reserved.
+        let none = unsafe { transmute::<Option<T>, u32>(None) };
+        let outer = unsafe { transmute::<Option<Option<T>>, u32>(None) };
+        assert!(T::reserved_slot(none).is_some());
+        assert!(T::reserved_slot(outer).is_some());
+        assert_ne!(none, outer);
+    }
+}
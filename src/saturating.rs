@@ -0,0 +1,187 @@
+//! Saturating arithmetic which clamps to the nearest allowed value.
+
+use core::fmt;
+
+/// Provides intentionally-saturated arithmetic on `NonMinX`/`NonMaxX` types.
+///
+/// This is the counterpart of `core::num::Saturating` for the types of this crate: the regular
+/// arithmetic operators on a `Saturating<T>` behave like the `saturating_*` methods of `T`. A
+/// result which would equal the excluded value is clamped to its nearest neighbour.
+///
+/// ```
+/// # use nonminmax::*;
+/// let x = Saturating(NonMaxU8::new(250).unwrap());
+/// let y = Saturating(NonMaxU8::new(10).unwrap());
+///
+/// // 255 is not a valid `NonMaxU8`, so the sum stops at 254.
+/// assert_eq!((x + y).0.get(), 254);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Saturating<T>(pub T);
+
+impl<T: fmt::Debug> fmt::Debug for Saturating<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Saturating<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+macro_rules! impl_saturating_ops {
+    ([$($gen:tt)*] $struct:ty, $prim:ty, $mask:expr, $neighbour:expr) => {
+        impl<$($gen)*> $struct {
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($struct), "`, replacing `",
+                stringify!($mask), "` by its nearest valid neighbour."),
+                #[inline]
+                pub fn saturating_new(value: $prim) -> Self {
+                    match Self::new(value) {
+                        Some(x) => x,
+                        None => unsafe { Self::new_unchecked($neighbour) },
+                    }
+                }
+            }
+
+            doc_comment! {
+                concat!("Creates an instance of `", stringify!($struct), "` by snapping `value` to ",
+                "the nearest valid value. This is an alias of `saturating_new`."),
+                #[inline]
+                pub fn clamp_new(value: $prim) -> Self {
+                    Self::saturating_new(value)
+                }
+            }
+
+            doc_comment! {
+                concat!("Saturating integer addition. Computes `self + rhs`, saturating at the ",
+                "numeric bounds of `", stringify!($struct), "` instead of overflowing."),
+                #[inline]
+                pub fn saturating_add(self, rhs: Self) -> Self {
+                    Self::saturating_new(<$prim>::saturating_add(self.get(), rhs.get()))
+                }
+            }
+
+            doc_comment! {
+                concat!("Saturating integer subtraction. Computes `self - rhs`, saturating at the ",
+                "numeric bounds of `", stringify!($struct), "` instead of overflowing."),
+                #[inline]
+                pub fn saturating_sub(self, rhs: Self) -> Self {
+                    Self::saturating_new(<$prim>::saturating_sub(self.get(), rhs.get()))
+                }
+            }
+
+            doc_comment! {
+                concat!("Saturating integer multiplication. Computes `self * rhs`, saturating at the ",
+                "numeric bounds of `", stringify!($struct), "` instead of overflowing."),
+                #[inline]
+                pub fn saturating_mul(self, rhs: Self) -> Self {
+                    Self::saturating_new(<$prim>::saturating_mul(self.get(), rhs.get()))
+                }
+            }
+
+            doc_comment! {
+                concat!("Saturating integer exponentiation. Computes `self.pow(exp)`, saturating at the ",
+                "numeric bounds of `", stringify!($struct), "` instead of overflowing."),
+                #[inline]
+                pub fn saturating_pow(self, exp: u32) -> Self {
+                    Self::saturating_new(<$prim>::saturating_pow(self.get(), exp))
+                }
+            }
+        }
+
+        impl_saturating_binop!([$($gen)*] $struct, Add, add, AddAssign, add_assign, saturating_add);
+        impl_saturating_binop!([$($gen)*] $struct, Sub, sub, SubAssign, sub_assign, saturating_sub);
+        impl_saturating_binop!([$($gen)*] $struct, Mul, mul, MulAssign, mul_assign, saturating_mul);
+    };
+}
+
+macro_rules! impl_saturating_binop {
+    ([$($gen:tt)*] $struct:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $saturating:ident) => {
+        impl<$($gen)*> core::ops::$trait for Saturating<$struct> {
+            type Output = Self;
+
+            #[inline]
+            fn $method(self, rhs: Self) -> Self {
+                Saturating(self.0.$saturating(rhs.0))
+            }
+        }
+
+        impl<$($gen)*> core::ops::$assign_trait for Saturating<$struct> {
+            #[inline]
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = <Self as core::ops::$trait>::$method(*self, rhs);
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    macro_rules! test_saturating {
+        ($test_name:ident, $struct:ident, $prim:ident, $mask:expr) => {
+            #[test]
+            fn $test_name() {
+                // the mask is mapped to its nearest neighbour.
+                let near = if $mask == <$prim>::MAX {
+                    $mask.wrapping_sub(1)
+                } else {
+                    $mask.wrapping_add(1)
+                };
+                assert_eq!($struct::saturating_new($mask).get(), near);
+                assert_eq!($struct::saturating_new(42).get(), 42);
+
+                // results stop at the numeric bounds of the type.
+                let lo = $struct::saturating_new(<$prim>::MIN);
+                let hi = $struct::saturating_new(<$prim>::MAX);
+                let one = $struct::new(1).unwrap();
+                let two = $struct::new(2).unwrap();
+                assert_eq!(hi.saturating_add(one), hi);
+                assert_eq!(hi.saturating_mul(two), hi);
+                assert_eq!(hi.saturating_pow(3), hi);
+                assert_eq!(lo.saturating_sub(one), lo);
+                assert_eq!(two.saturating_pow(3).get(), 8);
+
+                // the wrapper uses the same semantics.
+                let mut x = Saturating(hi);
+                x += Saturating(one);
+                assert_eq!(x, Saturating(hi));
+                assert_eq!((Saturating(two) * Saturating(two)).0.get(), 4);
+                assert_eq!((Saturating(lo) - Saturating(one)).0, lo);
+            }
+        };
+    }
+
+    test_saturating!(test_nonmaxu8, NonMaxU8, u8, u8::MAX);
+    test_saturating!(test_nonmaxu16, NonMaxU16, u16, u16::MAX);
+    test_saturating!(test_nonmaxu32, NonMaxU32, u32, u32::MAX);
+    test_saturating!(test_nonmaxu64, NonMaxU64, u64, u64::MAX);
+    test_saturating!(test_nonmaxu128, NonMaxU128, u128, u128::MAX);
+    test_saturating!(test_nonmaxusize, NonMaxUsize, usize, usize::MAX);
+
+    test_saturating!(test_nonmaxi8, NonMaxI8, i8, i8::MAX);
+    test_saturating!(test_nonmaxi16, NonMaxI16, i16, i16::MAX);
+    test_saturating!(test_nonmaxi32, NonMaxI32, i32, i32::MAX);
+    test_saturating!(test_nonmaxi64, NonMaxI64, i64, i64::MAX);
+    test_saturating!(test_nonmaxi128, NonMaxI128, i128, i128::MAX);
+    test_saturating!(test_nonmaxisize, NonMaxIsize, isize, isize::MAX);
+
+    test_saturating!(test_nonminu8, NonMinU8, u8, u8::MIN);
+    test_saturating!(test_nonminu16, NonMinU16, u16, u16::MIN);
+    test_saturating!(test_nonminu32, NonMinU32, u32, u32::MIN);
+    test_saturating!(test_nonminu64, NonMinU64, u64, u64::MIN);
+    test_saturating!(test_nonminu128, NonMinU128, u128, u128::MIN);
+    test_saturating!(test_nonminusize, NonMinUsize, usize, usize::MIN);
+
+    test_saturating!(test_nonmini8, NonMinI8, i8, i8::MIN);
+    test_saturating!(test_nonmini16, NonMinI16, i16, i16::MIN);
+    test_saturating!(test_nonmini32, NonMinI32, i32, i32::MIN);
+    test_saturating!(test_nonmini64, NonMinI64, i64, i64::MIN);
+    test_saturating!(test_nonmini128, NonMinI128, i128, i128::MIN);
+    test_saturating!(test_nonminisize, NonMinIsize, isize, isize::MIN);
+}
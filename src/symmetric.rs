@@ -0,0 +1,101 @@
+//! Operations on `NonMinIX` which cannot overflow.
+//!
+//! Excluding the minimum value of a signed integer makes its range symmetric around zero. As a
+//! result, negation and taking the absolute value can never overflow. Division can also only fail
+//! when dividing by zero, since `MIN / -1` is the only overflowing division, meaning that
+//! `checked_div` on these types returns `None` if and only if the divisor is zero.
+
+use crate::{NonMaxU128, NonMaxU16, NonMaxU32, NonMaxU64, NonMaxU8, NonMaxUsize};
+use crate::{NonMinI128, NonMinI16, NonMinI32, NonMinI64, NonMinI8, NonMinIsize};
+
+macro_rules! impl_symmetric {
+    ($struct:ident, $unsigned_struct:ident) => {
+        impl $struct {
+            /// Computes the absolute value of `self`. Unlike for the primitive type, this can
+            /// never overflow.
+            #[inline]
+            pub fn abs(self) -> Self {
+                unsafe { Self::new_unchecked(self.get().abs()) }
+            }
+
+            doc_comment! {
+                concat!("Computes the absolute value of `self` as a `", stringify!($unsigned_struct),
+                "`. The result can never be the maximum value of the unsigned type."),
+                #[inline]
+                pub fn unsigned_abs(self) -> $unsigned_struct {
+                    unsafe { $unsigned_struct::new_unchecked(self.get().unsigned_abs()) }
+                }
+            }
+
+            doc_comment! {
+                concat!("Computes the absolute difference between `self` and `other` as a `",
+                stringify!($unsigned_struct), "`. The largest possible difference is one less than ",
+                "the maximum value of the unsigned type."),
+                #[inline]
+                pub fn abs_diff(self, other: Self) -> $unsigned_struct {
+                    unsafe { $unsigned_struct::new_unchecked(self.get().abs_diff(other.get())) }
+                }
+            }
+        }
+
+        impl core::ops::Neg for $struct {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                unsafe { Self::new_unchecked(-self.get()) }
+            }
+        }
+    };
+}
+
+impl_symmetric!(NonMinI8, NonMaxU8);
+impl_symmetric!(NonMinI16, NonMaxU16);
+impl_symmetric!(NonMinI32, NonMaxU32);
+impl_symmetric!(NonMinI64, NonMaxU64);
+impl_symmetric!(NonMinI128, NonMaxU128);
+impl_symmetric!(NonMinIsize, NonMaxUsize);
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    macro_rules! test_symmetric {
+        ($test_name:ident, $struct:ident, $prim:ident, $unsigned:ident) => {
+            #[test]
+            fn $test_name() {
+                let lo = $struct::new(<$prim>::MIN + 1).unwrap();
+                let hi = $struct::new(<$prim>::MAX).unwrap();
+                let x = $struct::new(-42).unwrap();
+
+                assert_eq!(-x, $struct::new(42).unwrap());
+                assert_eq!(-lo, hi);
+                assert_eq!(-hi, lo);
+
+                assert_eq!(x.abs().get(), 42);
+                assert_eq!(lo.abs(), hi);
+
+                assert_eq!(x.unsigned_abs().get(), 42);
+                assert_eq!(lo.unsigned_abs().get(), <$prim>::MAX as $unsigned);
+
+                assert_eq!(x.abs_diff(-x).get(), 84);
+                assert_eq!(lo.abs_diff(hi).get(), <$unsigned>::MAX - 1);
+                assert_eq!(hi.abs_diff(lo).get(), <$unsigned>::MAX - 1);
+
+                // division only fails for a zero divisor.
+                let zero = $struct::new(0).unwrap();
+                let minus_one = $struct::new(-1).unwrap();
+                assert_eq!(lo.checked_div(minus_one), Some(hi));
+                assert_eq!(hi.checked_div(minus_one), Some(lo));
+                assert_eq!(lo.checked_div(zero), None);
+            }
+        };
+    }
+
+    test_symmetric!(test_nonmini8, NonMinI8, i8, u8);
+    test_symmetric!(test_nonmini16, NonMinI16, i16, u16);
+    test_symmetric!(test_nonmini32, NonMinI32, i32, u32);
+    test_symmetric!(test_nonmini64, NonMinI64, i64, u64);
+    test_symmetric!(test_nonmini128, NonMinI128, i128, u128);
+    test_symmetric!(test_nonminisize, NonMinIsize, isize, usize);
+}
@@ -0,0 +1,204 @@
+//! Wrapping arithmetic which skips over the excluded value.
+//!
+//! A type with `N` bits has `2^N - 1` valid values, so wrapping arithmetic is performed modulo
+//! `2^N - 1` instead of modulo `2^N`. The result is mapped back to the unique valid value within
+//! the same residue class, meaning that the excluded value is simply skipped.
+
+use core::fmt;
+
+/// Provides intentionally-wrapped arithmetic on `NonMinX`/`NonMaxX` types.
+///
+/// This is the counterpart of `core::num::Wrapping` for the types of this crate: the regular
+/// arithmetic operators on a `Wrapping<T>` behave like the `wrapping_*` methods of `T`, which wrap
+/// around modulo the number of valid values.
+///
+/// ```
+/// # use nonminmax::*;
+/// let x = Wrapping(NonMaxU8::new(254).unwrap());
+/// let one = Wrapping(NonMaxU8::new(1).unwrap());
+///
+/// // 255 is not a valid `NonMaxU8`, so the sequence wraps around to 0.
+/// assert_eq!((x + one).0.get(), 0);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Wrapping<T>(pub T);
+
+impl<T: fmt::Debug> fmt::Debug for Wrapping<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Wrapping<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+macro_rules! impl_wrapping_ops {
+    ([$($gen:tt)*] $struct:ty, $prim:ty, $mask:expr, $lowest:expr) => {
+        impl<$($gen)*> $struct {
+            /// The residue of the smallest valid value modulo `2^N - 1`. For signed types, a
+            /// negative `x` is stored as `x + 2^N ≡ x + 1`, which is corrected by subtracting one.
+            #[inline]
+            fn lowest_residue() -> <$prim as crate::niche::sealed::Sealed>::Unsigned {
+                let correction = if <$prim>::MIN != <$prim>::ZERO {
+                    <$prim>::ONE
+                } else {
+                    <$prim>::ZERO
+                };
+                <$prim>::to_unsigned(<$prim>::wrapping_sub($lowest, correction))
+            }
+
+            /// Returns the residue of `self` modulo `2^N - 1`.
+            #[inline]
+            fn to_residue(self) -> <$prim as crate::niche::sealed::Sealed>::Unsigned {
+                let index = <$prim>::to_unsigned(<$prim>::wrapping_sub(self.get(), $lowest));
+                crate::mersenne::MersenneWord::add(index, Self::lowest_residue())
+            }
+
+            /// Returns the unique valid value having the given residue modulo `2^N - 1`.
+            #[inline]
+            fn from_residue(residue: <$prim as crate::niche::sealed::Sealed>::Unsigned) -> Self {
+                let index = crate::mersenne::MersenneWord::sub(residue, Self::lowest_residue());
+                let value = <$prim>::wrapping_add($lowest, <$prim>::from_unsigned(index));
+                unsafe { Self::new_unchecked(value) }
+            }
+
+            doc_comment! {
+                concat!("Wrapping integer addition. Computes `self + rhs`, wrapping around modulo the ",
+                "number of valid values of `", stringify!($struct), "` (i.e., `", stringify!($mask), "` is skipped)."),
+                #[inline]
+                pub fn wrapping_add(self, rhs: Self) -> Self {
+                    Self::from_residue(crate::mersenne::MersenneWord::add(self.to_residue(), rhs.to_residue()))
+                }
+            }
+
+            doc_comment! {
+                concat!("Wrapping integer subtraction. Computes `self - rhs`, wrapping around modulo the ",
+                "number of valid values of `", stringify!($struct), "` (i.e., `", stringify!($mask), "` is skipped)."),
+                #[inline]
+                pub fn wrapping_sub(self, rhs: Self) -> Self {
+                    Self::from_residue(crate::mersenne::MersenneWord::sub(self.to_residue(), rhs.to_residue()))
+                }
+            }
+
+            doc_comment! {
+                concat!("Wrapping integer multiplication. Computes `self * rhs`, wrapping around modulo the ",
+                "number of valid values of `", stringify!($struct), "` (i.e., `", stringify!($mask), "` is skipped)."),
+                #[inline]
+                pub fn wrapping_mul(self, rhs: Self) -> Self {
+                    Self::from_residue(crate::mersenne::MersenneWord::mul(self.to_residue(), rhs.to_residue()))
+                }
+            }
+        }
+
+        impl_wrapping_binop!([$($gen)*] $struct, Add, add, AddAssign, add_assign, wrapping_add);
+        impl_wrapping_binop!([$($gen)*] $struct, Sub, sub, SubAssign, sub_assign, wrapping_sub);
+        impl_wrapping_binop!([$($gen)*] $struct, Mul, mul, MulAssign, mul_assign, wrapping_mul);
+    };
+}
+
+macro_rules! impl_wrapping_binop {
+    ([$($gen:tt)*] $struct:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $wrapping:ident) => {
+        impl<$($gen)*> core::ops::$trait for Wrapping<$struct> {
+            type Output = Self;
+
+            #[inline]
+            fn $method(self, rhs: Self) -> Self {
+                Wrapping(self.0.$wrapping(rhs.0))
+            }
+        }
+
+        impl<$($gen)*> core::ops::$assign_trait for Wrapping<$struct> {
+            #[inline]
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = <Self as core::ops::$trait>::$method(*self, rhs);
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    macro_rules! test_wrapping {
+        ($test_name:ident, $struct:ident, $prim:ident, $mask:expr) => {
+            #[test]
+            fn $test_name() {
+                let n = |x: $prim| $struct::new(x).unwrap();
+                let lo = $struct::saturating_new(<$prim>::MIN);
+                let hi = $struct::saturating_new(<$prim>::MAX);
+
+                // regular values behave like the primitive.
+                assert_eq!(n(12).wrapping_add(n(5)).get(), 17);
+                assert_eq!(n(12).wrapping_sub(n(5)).get(), 7);
+                assert_eq!(n(12).wrapping_mul(n(5)).get(), 60);
+
+                // stepping past the end wraps around to the start, skipping the mask.
+                assert_eq!(hi.wrapping_add(n(1)), lo);
+                assert_eq!(lo.wrapping_sub(n(1)), hi);
+                assert_eq!(hi.wrapping_add(n(2)), lo.wrapping_add(n(1)));
+
+                // the wrapper uses the same semantics.
+                let mut x = Wrapping(hi);
+                x += Wrapping(n(1));
+                assert_eq!(x, Wrapping(lo));
+                assert_eq!((Wrapping(n(3)) * Wrapping(n(4))).0.get(), 12);
+                assert_eq!((Wrapping(lo) - Wrapping(n(1))).0, hi);
+            }
+        };
+    }
+
+    test_wrapping!(test_nonmaxu8, NonMaxU8, u8, u8::MAX);
+    test_wrapping!(test_nonmaxu16, NonMaxU16, u16, u16::MAX);
+    test_wrapping!(test_nonmaxu32, NonMaxU32, u32, u32::MAX);
+    test_wrapping!(test_nonmaxu64, NonMaxU64, u64, u64::MAX);
+    test_wrapping!(test_nonmaxu128, NonMaxU128, u128, u128::MAX);
+    test_wrapping!(test_nonmaxusize, NonMaxUsize, usize, usize::MAX);
+
+    test_wrapping!(test_nonmaxi8, NonMaxI8, i8, i8::MAX);
+    test_wrapping!(test_nonmaxi16, NonMaxI16, i16, i16::MAX);
+    test_wrapping!(test_nonmaxi32, NonMaxI32, i32, i32::MAX);
+    test_wrapping!(test_nonmaxi64, NonMaxI64, i64, i64::MAX);
+    test_wrapping!(test_nonmaxi128, NonMaxI128, i128, i128::MAX);
+    test_wrapping!(test_nonmaxisize, NonMaxIsize, isize, isize::MAX);
+
+    test_wrapping!(test_nonminu8, NonMinU8, u8, u8::MIN);
+    test_wrapping!(test_nonminu16, NonMinU16, u16, u16::MIN);
+    test_wrapping!(test_nonminu32, NonMinU32, u32, u32::MIN);
+    test_wrapping!(test_nonminu64, NonMinU64, u64, u64::MIN);
+    test_wrapping!(test_nonminu128, NonMinU128, u128, u128::MIN);
+    test_wrapping!(test_nonminusize, NonMinUsize, usize, usize::MIN);
+
+    test_wrapping!(test_nonmini8, NonMinI8, i8, i8::MIN);
+    test_wrapping!(test_nonmini16, NonMinI16, i16, i16::MIN);
+    test_wrapping!(test_nonmini32, NonMinI32, i32, i32::MIN);
+    test_wrapping!(test_nonmini64, NonMinI64, i64, i64::MIN);
+    test_wrapping!(test_nonmini128, NonMinI128, i128, i128::MIN);
+    test_wrapping!(test_nonminisize, NonMinIsize, isize, isize::MIN);
+
+    #[test]
+    fn test_exhaustive_i8() {
+        // compare against a reference implementation using wider integers.
+        let wrap = |x: i32, lo: i32| (x - lo).rem_euclid(255) + lo;
+
+        for a in -128..=127i32 {
+            for b in -128..=127i32 {
+                if let (Some(x), Some(y)) = (NonMaxI8::new(a as i8), NonMaxI8::new(b as i8)) {
+                    assert_eq!(x.wrapping_add(y).get() as i32, wrap(a + b, -128));
+                    assert_eq!(x.wrapping_sub(y).get() as i32, wrap(a - b, -128));
+                    assert_eq!(x.wrapping_mul(y).get() as i32, wrap(a * b, -128));
+                }
+
+                if let (Some(x), Some(y)) = (NonMinI8::new(a as i8), NonMinI8::new(b as i8)) {
+                    assert_eq!(x.wrapping_add(y).get() as i32, wrap(a + b, -127));
+                    assert_eq!(x.wrapping_sub(y).get() as i32, wrap(a - b, -127));
+                    assert_eq!(x.wrapping_mul(y).get() as i32, wrap(a * b, -127));
+                }
+            }
+        }
+    }
+}